@@ -33,7 +33,21 @@ criterion_group! {
     config = Criterion::default()
         .warm_up_time(Duration::from_secs(5))
         .measurement_time(Duration::from_secs(60));
-    targets = hadron_bench, oracle_bench
+    targets = hadron_bench, hadron_pow2_bench, oracle_bench
+}
+
+/// Wrapper so [`run_bench`] can construct a pow2-capacity [`Hadron`] the same
+/// way it constructs every other contender via `with_capacity`.
+struct HadronPow2<T>(Hadron<T>);
+
+impl<T: Copy + std::fmt::Debug> HadronPow2<T> {
+    fn with_capacity(capacity: usize) -> Self {
+        Self(Hadron::with_capacity_pow2(capacity))
+    }
+
+    fn append_from_slice(&mut self, items: &[T]) {
+        self.0.copy_from_slice(items);
+    }
 }
 
 macro_rules! run_bench {
@@ -73,4 +87,5 @@ macro_rules! run_bench {
 }
 
 run_bench!(hadron_bench, Hadron<Log>, "hadron");
+run_bench!(hadron_pow2_bench, HadronPow2<Log>, "hadron_pow2");
 run_bench!(oracle_bench, Oracle<Log>, "oracle");
@@ -0,0 +1,111 @@
+//! Append-only cold storage for records evicted under [`Trimmer::Compress`](crate::segment::Trimmer::Compress).
+
+use crate::lz;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// Metadata describing one compressed block written to a spill file.
+#[derive(Debug)]
+struct SpillBlock {
+    /// Logical index, among every record ever pushed into the owning Segment, of the
+    /// first record this block holds.
+    start: usize,
+    /// Number of records this block holds.
+    count: usize,
+    /// Byte offset into the spill file where this block's compressed bytes begin.
+    offset: u64,
+    /// Length, in bytes, of this block's compressed bytes on disk.
+    compressed_len: usize,
+    /// Length, in bytes, of this block once decompressed.
+    decompressed_len: usize,
+}
+
+/// Cold storage backing [`Trimmer::Compress`](crate::segment::Trimmer::Compress): an
+/// append-only file of LZ77-compressed blocks, indexed in memory so a caller crossing
+/// into spilled territory can find and lazily decompress the block that covers it.
+#[derive(Debug)]
+pub(crate) struct Cold {
+    file: File,
+    blocks: Vec<SpillBlock>,
+    len: u64,
+}
+
+impl Cold {
+    /// Open (creating if needed) the spill file at `path`, ready to append new blocks.
+    ///
+    /// The in-memory block index always starts empty; blocks already on disk from a
+    /// prior process are appended-past rather than re-read, since this is a write-through
+    /// cache for records this `Segment` has itself evicted this run.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the spill file.
+    pub(crate) fn open(path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(path)?;
+
+        Ok(Self {
+            file,
+            blocks: Vec::new(),
+            len: 0,
+        })
+    }
+
+    /// Compress `bytes` and append it to the spill file as a new block.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - Logical index of the first record `bytes` holds.
+    /// * `bytes` - Raw bytes of the evicted records, in order.
+    /// * `record_size` - Size in bytes of a single record, used to recover `bytes.len() / record_size`.
+    /// * `window` - LZ77 match window to compress `bytes` with.
+    pub(crate) fn spill(
+        &mut self,
+        start: usize,
+        bytes: &[u8],
+        record_size: usize,
+        window: usize,
+    ) -> io::Result<()> {
+        // Blocks are only ever appended for records evicted in FIFO order, so each new
+        // block's logical range should pick up exactly where the last one left off.
+        debug_assert!(
+            self.blocks
+                .last()
+                .is_none_or(|last| last.start + last.count == start)
+        );
+
+        let compressed = lz::encode(bytes, window);
+        self.file.write_all(&compressed)?;
+
+        self.blocks.push(SpillBlock {
+            start,
+            count: bytes.len() / record_size,
+            offset: self.len,
+            compressed_len: compressed.len(),
+            decompressed_len: bytes.len(),
+        });
+        self.len += compressed.len() as u64;
+
+        Ok(())
+    }
+
+    /// Number of blocks spilled so far.
+    pub(crate) fn block_count(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Read and decompress the block at `index` back into raw record bytes.
+    pub(crate) fn read_block(&mut self, index: usize) -> io::Result<Vec<u8>> {
+        let block = &self.blocks[index];
+
+        let mut compressed = vec![0u8; block.compressed_len];
+        self.file.seek(SeekFrom::Start(block.offset))?;
+        self.file.read_exact(&mut compressed)?;
+
+        Ok(lz::decode(&compressed, block.decompressed_len))
+    }
+}
@@ -0,0 +1,104 @@
+//! Table-driven CRC-64 checksum used to detect corruption of a [`Segment`](crate::Segment)'s
+//! live record region.
+
+/// Polynomial used by this CRC-64 variant.
+const POLY: u64 = 0x42F0E1EBA9EA3693;
+
+/// Precomputed remainder for every possible byte value, so [`Crc64::update`] processes
+/// one byte per table lookup instead of shifting through it bit by bit.
+static TABLE: [u64; 256] = build_table();
+
+/// Build the lookup table by shifting each byte through the polynomial.
+const fn build_table() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut byte = 0usize;
+
+    while byte < 256 {
+        let mut crc = (byte as u64) << 56;
+        let mut bit = 0;
+
+        while bit < 8 {
+            crc = if crc & (1 << 63) != 0 {
+                (crc << 1) ^ POLY
+            } else {
+                crc << 1
+            };
+            bit += 1;
+        }
+
+        table[byte] = crc;
+        byte += 1;
+    }
+
+    table
+}
+
+/// Running CRC-64 checksum that bytes can be folded into incrementally.
+///
+/// Folding in bytes as they're appended costs O(appended bytes), so a [`Segment`](crate::Segment)
+/// can keep this up to date on every `push`/`extend_from_slice` instead of rehashing the
+/// whole live region each time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Crc64(u64);
+
+impl Crc64 {
+    /// Start a new checksum with no bytes folded in yet.
+    pub(crate) fn new() -> Self {
+        Self(!0u64)
+    }
+
+    /// Fold more bytes into this checksum.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Bytes to fold into the running checksum.
+    pub(crate) fn update(&mut self, bytes: &[u8]) {
+        let mut crc = self.0;
+
+        for &byte in bytes {
+            crc = TABLE[((crc >> 56) as u8 ^ byte) as usize] ^ (crc << 8);
+        }
+
+        self.0 = crc;
+    }
+
+    /// Finalize this checksum into its externally visible value.
+    pub(crate) fn finalize(self) -> u64 {
+        !self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn of(bytes: &[u8]) -> u64 {
+        let mut crc = Crc64::new();
+        crc.update(bytes);
+        crc.finalize()
+    }
+
+    #[test]
+    fn test_empty_input() {
+        assert_eq!(of(b""), 0);
+    }
+
+    #[test]
+    fn test_known_vector() {
+        assert_eq!(of(b"123456789"), 0x62ec59e3f1a4f00a);
+    }
+
+    #[test]
+    fn test_incremental_matches_one_shot() {
+        let mut incremental = Crc64::new();
+        incremental.update(b"123");
+        incremental.update(b"456");
+        incremental.update(b"789");
+        assert_eq!(incremental.finalize(), of(b"123456789"));
+    }
+
+    #[test]
+    fn test_different_bytes_differ() {
+        assert_ne!(of(b"abc"), of(b"abd"));
+    }
+}
@@ -0,0 +1,32 @@
+//! Definition of errors for fallible memory allocation.
+
+use thiserror::Error;
+
+/// Errors that can happen attempting a fallible memory allocation.
+///
+/// Mirrors the shape of the standard library's `try_reserve` APIs, so callers
+/// that want to degrade gracefully instead of aborting the process on OOM
+/// (for example a server allocating a very large off-heap [`Segment`](crate::Segment))
+/// have somewhere to route the failure.
+#[derive(Debug, Error)]
+pub enum TryReserveError {
+    /// The requested capacity exceeds `isize::MAX` bytes once the element
+    /// size and alignment are accounted for.
+    #[error("Requested capacity exceeds isize::MAX bytes")]
+    CapacityOverflow,
+
+    /// The allocator returned an error/null pointer for a valid layout.
+    #[error("Memory allocator failed to allocate the requested memory")]
+    AllocError,
+}
+
+// `TryReserveError` (`std::collections::TryReserveError` re-exports the `alloc` one) does
+// not expose which of the above cases occurred on stable Rust, so both collapse to
+// `AllocError` here. Implemented against `alloc::collections::TryReserveError` directly
+// so this conversion is available under `#![no_std]` too, where `std::collections` doesn't
+// exist but `Vec::try_reserve_exact` (used by e.g. `VecStorage`/`RingStorage`) still does.
+impl From<alloc::collections::TryReserveError> for TryReserveError {
+    fn from(_: alloc::collections::TryReserveError) -> Self {
+        TryReserveError::AllocError
+    }
+}
@@ -0,0 +1,168 @@
+//! [`std::io::Read`]/[`std::io::Write`] adapters over byte-valued Segments, so a
+//! `VecSegment<u8>`/`MmapSegment<u8>` can plug into the wider std ecosystem (serializers,
+//! hashers, compressors) without manual `extend_from_slice`/`records().iter()` glue.
+
+use crate::{Segment, Storage};
+use std::cmp::min;
+use std::io::{self, Read, Write};
+
+/// Adapter that implements [`Write`] by appending bytes to a Segment.
+///
+/// Each [`write`](Write::write) call is a single `extend_from_slice`, so it honors whatever
+/// [`Trimmer`](crate::Trimmer) the Segment was configured with when capacity runs out;
+/// [`flush`](Write::flush) is a no-op since there is no intermediate buffering.
+pub struct SegmentWriter<'a, S: Storage<Record = u8>> {
+    segment: &'a mut Segment<S>,
+}
+
+impl<'a, S: Storage<Record = u8>> SegmentWriter<'a, S> {
+    /// Wrap `segment` so bytes written to it are appended via `extend_from_slice`.
+    pub fn new(segment: &'a mut Segment<S>) -> Self {
+        Self { segment }
+    }
+}
+
+impl<S: Storage<Record = u8>> Write for SegmentWriter<'_, S> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let rejected = self.segment.extend_from_slice(buf).len();
+        Ok(buf.len() - rejected)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Adapter that implements [`Read`] as a streaming cursor over a Segment's live bytes.
+///
+/// Holds only a logical offset into the Segment, never copying its contents up front, so
+/// `read_to_end`, [`io::copy`], and [`io::BufReader`] all work directly against the ring.
+/// A read that straddles the wraparound boundary of a [`RingSegment`](crate::RingSegment)
+/// is filled from both physical runs transparently, so callers see a flat byte stream.
+pub struct SegmentReader<'a, S: Storage<Record = u8>> {
+    segment: &'a Segment<S>,
+    offset: usize,
+}
+
+impl<'a, S: Storage<Record = u8>> SegmentReader<'a, S> {
+    /// Wrap `segment` so it can be read as a flat byte stream, starting at its first record.
+    pub fn new(segment: &'a Segment<S>) -> Self {
+        Self { segment, offset: 0 }
+    }
+}
+
+impl<S: Storage<Record = u8>> Read for SegmentReader<'_, S> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let (first, second) = self.segment.as_slices();
+        let mut written = 0;
+
+        if self.offset < first.len() {
+            let start = self.offset;
+            let n = min(buf.len(), first.len() - start);
+            buf[..n].copy_from_slice(&first[start..start + n]);
+            written += n;
+            self.offset += n;
+        }
+
+        if written < buf.len() {
+            let start = self.offset - first.len();
+            if start < second.len() {
+                let n = min(buf.len() - written, second.len() - start);
+                buf[written..written + n].copy_from_slice(&second[start..start + n]);
+                written += n;
+                self.offset += n;
+            }
+        }
+
+        Ok(written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Trimmer, VecSegment};
+
+    #[test]
+    fn test_writer_appends_via_extend_from_slice() {
+        let mut segment = VecSegment::with_capacity(8, Trimmer::None);
+        let mut writer = SegmentWriter::new(&mut segment);
+
+        assert_eq!(writer.write(b"hello").unwrap(), 5);
+        assert!(writer.flush().is_ok());
+        assert_eq!(segment.records(), b"hello");
+    }
+
+    #[test]
+    fn test_writer_reports_rejected_bytes_when_capacity_runs_out() {
+        let mut segment = VecSegment::with_capacity(4, Trimmer::None);
+        let mut writer = SegmentWriter::new(&mut segment);
+
+        assert_eq!(writer.write(b"hello").unwrap(), 4);
+        assert_eq!(segment.records(), b"hell");
+    }
+
+    #[test]
+    fn test_reader_streams_records_and_advances_offset() {
+        let mut segment = VecSegment::with_capacity(8, Trimmer::None);
+        assert!(segment.extend_from_slice(b"hello").is_empty());
+
+        let mut reader = SegmentReader::new(&segment);
+        let mut out = Vec::new();
+        reader.read_to_end(&mut out).unwrap();
+        assert_eq!(out, b"hello");
+
+        // Exhausted reader yields no further bytes.
+        assert_eq!(reader.read(&mut [0u8; 4]).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_reader_fills_caller_buffer_across_multiple_reads() {
+        let mut segment = VecSegment::with_capacity(8, Trimmer::None);
+        assert!(segment.extend_from_slice(b"abcdef").is_empty());
+
+        let mut reader = SegmentReader::new(&segment);
+        let mut first = [0u8; 4];
+        assert_eq!(reader.read(&mut first).unwrap(), 4);
+        assert_eq!(&first, b"abcd");
+
+        let mut second = [0u8; 4];
+        assert_eq!(reader.read(&mut second).unwrap(), 2);
+        assert_eq!(&second[..2], b"ef");
+    }
+
+    #[test]
+    fn test_reader_handles_ring_wraparound_boundary() {
+        use crate::RingSegment;
+
+        let mut segment: RingSegment<u8> = RingSegment::with_capacity(4, Trimmer::None);
+        assert!(segment.extend_from_slice(b"abcd").is_empty());
+        segment.trim(2);
+        assert!(segment.extend_from_slice(b"ef").is_empty());
+
+        // Physically wrapped: "cd" then "ef"; the reader should still yield a flat stream.
+        let mut reader = SegmentReader::new(&segment);
+        let mut out = Vec::new();
+        reader.read_to_end(&mut out).unwrap();
+        assert_eq!(out, b"cdef");
+    }
+
+    #[test]
+    fn test_reader_read_straddles_wraparound_boundary() {
+        use crate::RingSegment;
+
+        let mut segment: RingSegment<u8> = RingSegment::with_capacity(4, Trimmer::None);
+        assert!(segment.extend_from_slice(b"abcd").is_empty());
+        segment.trim(2);
+        assert!(segment.extend_from_slice(b"ef").is_empty());
+
+        let mut reader = SegmentReader::new(&segment);
+        let mut buf = [0u8; 3];
+        assert_eq!(reader.read(&mut buf).unwrap(), 3);
+        assert_eq!(&buf, b"cde");
+
+        let mut rest = [0u8; 3];
+        assert_eq!(reader.read(&mut rest).unwrap(), 1);
+        assert_eq!(&rest[..1], b"f");
+    }
+}
@@ -26,6 +26,8 @@
 //!
 //! * [`Trimmer::Nothing`] - Do nothing.
 //! * [`Trimmer::Trim`] - Remove N records from the beginning of Segment.
+//! * [`Trimmer::Compress`] - Spill evicted records, LZ77-compressed, to an append-only file
+//!   instead of discarding them; still readable afterwards through [`Segment::iter`].
 //!
 //! ### Storage
 //!
@@ -35,6 +37,58 @@
 //!
 //! * [`VecStorage`] - Storage engine backed by [`Vec`] with global allocator.
 //! * [`MmapStorage`] - Storage engine backed by anonymous mmap for memory.
+//! * [`MagicStorage`] - Linux-only double-mapped ring buffer; trims in O(1) like
+//!   [`RingStorage`] but, thanks to the mirrored mapping, never needs
+//!   [`Storage::records_slices`] to read around the wrap.
+//!
+//! ### Integrity
+//!
+//! A [`Segment`] can optionally maintain a running CRC-64 checksum over its live records.
+//! Call [`Segment::enable_integrity`] to turn it on, [`Segment::checksum`] to read the
+//! current value, and [`Segment::verify`] to detect corruption of the underlying buffer.
+//! This matters most for long-lived, memory-mapped segments like [`MmapSegment`], where a
+//! stray out-of-bounds write elsewhere in the process is otherwise silent.
+//!
+//! ### Sync
+//!
+//! Behind the `sync` feature, [`AtomicMemStorage`](crate::sync::AtomicMemStorage) is
+//! a storage engine whose append and trim offsets are a pair of atomics instead of
+//! plain fields, with appends published by a release store. No [`Segment`] type in
+//! this crate is built on it, and its mutating methods take `&mut self`, so it does
+//! not by itself split a segment across a producer and a consumer thread. Off by default, so the common single-threaded path pays no atomic
+//! overhead.
+//!
+//! ### Ring
+//!
+//! Behind the `mmap` feature, [`Ring`](crate::ring::Ring) is a circular buffer that avoids
+//! [`Segment`]'s O(n) left-shift on [`Trimmer::Trim`] by keeping a `read_at` index and
+//! wrapping writes around the end of the buffer instead, so [`Ring::trim`](crate::ring::Ring::trim)
+//! is O(1). It can grow/shrink in place via [`Ring::reserve`](crate::ring::Ring::reserve)/
+//! [`Ring::shrink_to`](crate::ring::Ring::shrink_to), persist to disk via
+//! [`Ring::open`](crate::ring::Ring::open), and split into a lock-free
+//! [`Producer`](crate::ring::Producer)/[`Consumer`](crate::ring::Consumer) pair via
+//! [`Ring::split`](crate::ring::Ring::split). [`Ring::freeze_range`](crate::ring::Ring::freeze_range)
+//! hands a range of live records downstream without copying, as a reference-counted
+//! [`RingBytes`](crate::ring::RingBytes) view that keeps the mapping alive and pins it
+//! against in-place mutation for as long as the view is outstanding.
+//!
+//! ### Io
+//!
+//! A byte-valued Segment plugs into the wider [`std::io`] ecosystem through
+//! [`SegmentWriter`] and [`SegmentReader`], so serializers, hashers, and compressors can
+//! target a Segment without manual `extend_from_slice`/`records().iter()` glue.
+//!
+//! ### no_std
+//!
+//! With default features disabled (`default-features = false`), this crate builds under
+//! `#![no_std]` with only `extern crate alloc`, keeping [`Storage`], [`VecStorage`], and
+//! [`OnHeapStorage`](crate::OnHeapStorage) available on targets with no OS (embedded,
+//! `wasm32`). The `std` feature (on by default) brings back [`Segment`] and everything
+//! built on top of it, which needs a filesystem for [`Trimmer::Compress`] spill files and
+//! the [`SegmentReader`]/[`SegmentWriter`] [`std::io`] adapters. The `mmap` feature (on by
+//! default, requires `std`) additionally gates [`MmapStorage`] and its `memmap2`
+//! dependency, so a pure-`alloc` ring is still an option on platforms where anonymous mmap
+//! isn't available.
 //!
 //! ### Example
 //!
@@ -67,11 +121,38 @@
 //! assert_eq!(segment.records(), &[4, 5, 6]);
 //!```
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub(crate) mod cold;
+pub(crate) mod crc;
+pub(crate) mod error;
+#[cfg(feature = "std")]
+pub(crate) mod io;
+#[cfg(feature = "std")]
+pub(crate) mod lz;
 pub(crate) mod record;
+#[cfg(feature = "mmap")]
+pub(crate) mod ring;
+#[cfg(feature = "std")]
 pub(crate) mod segment;
 pub(crate) mod storage;
+#[cfg(feature = "sync")]
+pub(crate) mod sync;
 
 // Externally exposed types.
+pub use error::TryReserveError;
+#[cfg(feature = "std")]
+pub use io::{SegmentReader, SegmentWriter};
 pub use record::Record;
-pub use segment::{MmapSegment, Segment, Trimmer, VecSegment};
-pub use storage::{MmapStorage, Storage, VecStorage};
+#[cfg(feature = "std")]
+pub use segment::{
+    Drain, InlineSegment, Iter, MmapSegment, RingSegment, Segment, Trimmer, VecSegment,
+};
+#[cfg(feature = "mmap")]
+pub use storage::{HugePageSize, MmapStorage};
+#[cfg(all(feature = "mmap", target_os = "linux"))]
+pub use storage::MagicStorage;
+pub use storage::{InlineStorage, RingStorage, Storage, VecStorage};
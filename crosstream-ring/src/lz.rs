@@ -0,0 +1,204 @@
+//! Classic LZ77 compressor used to spill evicted records to cold storage under
+//! [`Trimmer::Compress`](crate::segment::Trimmer::Compress).
+//!
+//! Output is a stream of tokens: a `0` tag byte followed by one raw literal byte, or a `1`
+//! tag byte followed by a little-endian `(offset: u32, length: u16)` copy instruction.
+
+use std::collections::HashMap;
+
+/// Shortest run encoded as a copy token; anything shorter is emitted as literals.
+const MIN_MATCH: usize = 3;
+
+/// Longest run a single copy token can encode; bounded by the `u16` length field.
+const MAX_MATCH: usize = u16::MAX as usize;
+
+/// Longest hash chain walked per position when searching for the best match, so
+/// pathological inputs (e.g. all-zero buffers) can't make encoding quadratic.
+const MAX_CHAIN: usize = 32;
+
+/// Compress `input`, searching for matches no further than `window` bytes back.
+///
+/// Slides over `input` one position at a time, hashing every 3-byte prefix into chains of
+/// prior positions that shared it, and at each position finds the longest match within
+/// `window` bytes behind. Emits a literal token if no match of at least [`MIN_MATCH`] bytes
+/// is found, otherwise a copy token.
+pub(crate) fn encode(input: &[u8], window: usize) -> Vec<u8> {
+    let window = window.max(1);
+    let mut output = Vec::new();
+
+    // Most recent position seen for a given 3-byte prefix, and a linked chain of earlier
+    // positions sharing that same prefix, searched back-to-front up to `MAX_CHAIN` deep.
+    let mut heads: HashMap<[u8; 3], usize> = HashMap::new();
+    let mut prev: Vec<usize> = vec![usize::MAX; input.len()];
+
+    let mut pos = 0;
+    while pos < input.len() {
+        match find_match(input, pos, window, &heads, &prev) {
+            Some((offset, length)) => {
+                output.push(1);
+                output.extend_from_slice(&(offset as u32).to_le_bytes());
+                output.extend_from_slice(&(length as u16).to_le_bytes());
+
+                for i in pos..pos + length {
+                    insert_hash(input, i, &mut heads, &mut prev);
+                }
+                pos += length;
+            }
+            None => {
+                output.push(0);
+                output.push(input[pos]);
+
+                insert_hash(input, pos, &mut heads, &mut prev);
+                pos += 1;
+            }
+        }
+    }
+
+    output
+}
+
+/// Decompress a token stream produced by [`encode`] back into `original_len` bytes.
+pub(crate) fn decode(data: &[u8], original_len: usize) -> Vec<u8> {
+    let mut output = Vec::with_capacity(original_len);
+    let mut pos = 0;
+
+    while pos < data.len() {
+        match data[pos] {
+            0 => {
+                output.push(data[pos + 1]);
+                pos += 2;
+            }
+            1 => {
+                let offset =
+                    u32::from_le_bytes(data[pos + 1..pos + 5].try_into().unwrap()) as usize;
+                let length =
+                    u16::from_le_bytes(data[pos + 5..pos + 7].try_into().unwrap()) as usize;
+                pos += 7;
+
+                // Copy byte-by-byte rather than via a slice, since `offset < length` is a
+                // valid (and common, e.g. run-length) overlapping copy.
+                let start = output.len() - offset;
+                for i in 0..length {
+                    output.push(output[start + i]);
+                }
+            }
+            tag => unreachable!("Unknown LZ77 token tag {tag}"),
+        }
+    }
+
+    output
+}
+
+/// Record `pos` in the hash chain for the 3-byte prefix starting there, if one exists.
+fn insert_hash(input: &[u8], pos: usize, heads: &mut HashMap<[u8; 3], usize>, prev: &mut [usize]) {
+    if pos + 3 > input.len() {
+        return;
+    }
+
+    let key = [input[pos], input[pos + 1], input[pos + 2]];
+    if let Some(head) = heads.insert(key, pos) {
+        prev[pos] = head;
+    }
+}
+
+/// Find the longest match for the bytes starting at `pos`, no further than `window` bytes
+/// behind it, by walking the hash chain for its 3-byte prefix.
+fn find_match(
+    input: &[u8],
+    pos: usize,
+    window: usize,
+    heads: &HashMap<[u8; 3], usize>,
+    prev: &[usize],
+) -> Option<(usize, usize)> {
+    if pos + 3 > input.len() {
+        return None;
+    }
+
+    let key = [input[pos], input[pos + 1], input[pos + 2]];
+    let mut candidate = *heads.get(&key)?;
+    let lower_bound = pos.saturating_sub(window);
+    let max_len = MAX_MATCH.min(input.len() - pos);
+
+    let mut best: Option<(usize, usize)> = None;
+    let mut chain = 0;
+
+    loop {
+        if candidate < lower_bound || chain >= MAX_CHAIN {
+            break;
+        }
+
+        let mut length = 0;
+        while length < max_len && input[candidate + length] == input[pos + length] {
+            length += 1;
+        }
+
+        let improves = match best {
+            Some((_, best_len)) => length > best_len,
+            None => length >= MIN_MATCH,
+        };
+        if improves && length >= MIN_MATCH {
+            best = Some((pos - candidate, length));
+        }
+
+        if candidate == 0 {
+            break;
+        }
+
+        match prev[candidate] {
+            usize::MAX => break,
+            next => {
+                candidate = next;
+                chain += 1;
+            }
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bolero::check;
+
+    fn round_trip(input: &[u8], window: usize) {
+        let compressed = encode(input, window);
+        assert_eq!(decode(&compressed, input.len()), input);
+    }
+
+    #[test]
+    fn test_empty_input() {
+        round_trip(b"", 64);
+    }
+
+    #[test]
+    fn test_no_repetition() {
+        round_trip(b"abcdefg", 64);
+    }
+
+    #[test]
+    fn test_repeated_pattern_compresses() {
+        let input = b"abcabcabcabcabcabcabcabcabcabc";
+        let compressed = encode(input, 64);
+        assert!(compressed.len() < input.len());
+        round_trip(input, 64);
+    }
+
+    #[test]
+    fn test_long_run_of_one_byte() {
+        round_trip(&[7u8; 4096], 64);
+    }
+
+    #[test]
+    fn test_match_crossing_window_boundary_is_skipped() {
+        // A window of 1 rules out every match; this should fall back to all literals.
+        round_trip(b"abcabcabcabc", 1);
+    }
+
+    #[test]
+    fn test_round_trip_property() {
+        check!()
+            .with_type::<Vec<u8>>()
+            .for_each(|input| round_trip(input, 32));
+    }
+}
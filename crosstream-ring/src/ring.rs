@@ -2,17 +2,68 @@
 
 use crate::Record;
 use memmap2::{MmapMut, MmapOptions};
+use std::cell::UnsafeCell;
 use std::cmp::min;
-use std::{borrow::Borrow, marker::PhantomData, ops::Deref};
+use std::fs::{File, OpenOptions};
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{borrow::Borrow, io, marker::PhantomData, ops::Deref, ops::Range};
 
+/// Identifies a file as holding a [`Ring::open`] header in the format below, so an
+/// unrelated file (or a stale header layout) is rejected rather than silently misread.
+const RING_MAGIC: &[u8; 8] = b"XSRING01";
+
+/// Byte offsets of each header field within a [`Ring::open`] mapping.
+const RECORD_SIZE_OFFSET: usize = 8;
+const CAPACITY_OFFSET: usize = 16;
+const READ_AT_OFFSET: usize = 24;
+const LENGTH_OFFSET: usize = 32;
+
+/// Total size, in bytes, of the header above; record bytes start right after it.
+const HEADER_SIZE: usize = 40;
+
+/// A circular buffer of [`Record`]s backed by a memory mapping, anonymous by
+/// default ([`Ring::with_capacity`]) or file-backed for durability ([`Ring::open`]).
+///
+/// Unlike [`crate::VecStorage`]/[`crate::OffHeapStorage`], which always start
+/// the live records at byte offset `0` and left-shift survivors on every
+/// [`Ring::trim`], this keeps a `read_at` index and wraps writes around the end
+/// of the buffer, so [`Ring::trim`] is O(1) instead of O(n). The tradeoff is
+/// that records can straddle the end of the buffer; [`Ring::deref`] only works
+/// while they're contiguous, use [`Ring::as_slices`] once they've wrapped.
 #[derive(Debug)]
 pub struct Ring<T> {
+    /// Record index of the oldest live record.
+    read_at: usize,
     len: usize,
     cap: usize,
-    memory: MmapMut,
+    /// Byte offset where record storage starts in `memory`; `0` for
+    /// [`Ring::with_capacity`], [`HEADER_SIZE`] for [`Ring::open`].
+    header: usize,
+    /// Shared so a [`RingBytes`] view returned by [`Ring::freeze_range`] can keep
+    /// the mapping alive past this Ring's lifetime; every in-place mutator goes
+    /// through [`Ring::memory_mut`], which panics while such a view is outstanding.
+    memory: Arc<MmapMut>,
+    /// Backing file, if this Ring was opened via [`Ring::open`]; kept around so
+    /// [`Ring::reserve`]/[`Ring::shrink_to`] can resize it before remapping.
+    file: Option<File>,
     phantom: PhantomData<T>,
 }
 
+/// Snapshot of a [`Ring`]'s occupancy and capacity, returned by [`Ring::limits`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RingLimits {
+    /// Number of records currently held.
+    pub len: usize,
+    /// Total record capacity, occupied or free.
+    pub capacity: usize,
+    /// Number of bytes currently occupied by live records.
+    pub bytes_used: usize,
+    /// Total capacity in bytes, occupied or free.
+    pub bytes_capacity: usize,
+}
+
 impl<T: Record> Ring<T> {
     pub fn with_capacity(capacity: usize) -> Self {
         let mmap = MmapOptions::new()
@@ -21,10 +72,144 @@ impl<T: Record> Ring<T> {
             .expect("Cannot allocate memory for segment");
 
         Self {
+            read_at: 0,
             cap: capacity,
             len: 0,
+            header: 0,
             phantom: PhantomData,
-            memory: mmap,
+            memory: Arc::new(mmap),
+            file: None,
+        }
+    }
+
+    /// Open (creating if needed) a file-backed [`Ring`] for `capacity` records at `path`.
+    ///
+    /// If `path` already holds a header whose magic and `T::size()`/`capacity` all
+    /// match, `read_at`/`len` are restored from it, so an already-populated Ring
+    /// survives a restart; if the magic matches but `T::size()` or `capacity` does
+    /// not, this returns an [`io::ErrorKind::InvalidData`] error. A file without the
+    /// magic gets a fresh, empty header, after being sized for `capacity` records if
+    /// it was empty. Record bytes are written straight into the mapping as they're
+    /// appended, but are only guaranteed durable once [`Ring::flush`]/
+    /// [`Ring::flush_range`] returns.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the backing file.
+    /// * `capacity` - Maximum number of records this Ring can hold.
+    pub fn open<P: AsRef<Path>>(path: P, capacity: usize) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .write(true)
+            .open(path)?;
+
+        let total_len = HEADER_SIZE + capacity * T::size();
+        if file.metadata()?.len() == 0 {
+            file.set_len(total_len as u64)?;
+        }
+
+        // Safety: `file` stays open for the lifetime of the mapping and isn't
+        // truncated by any other code path in this crate.
+        let mut mmap = unsafe { MmapOptions::new().len(total_len).map_mut(&file)? };
+
+        let (read_at, len) = if &mmap[..8] == RING_MAGIC {
+            let found_record_size = read_u64(&mmap, RECORD_SIZE_OFFSET) as usize;
+            let found_capacity = read_u64(&mmap, CAPACITY_OFFSET) as usize;
+
+            if found_record_size != T::size() || found_capacity != capacity {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "Ring file header does not match this record type/capacity",
+                ));
+            }
+
+            (
+                read_u64(&mmap, READ_AT_OFFSET) as usize,
+                read_u64(&mmap, LENGTH_OFFSET) as usize,
+            )
+        } else {
+            mmap[..8].copy_from_slice(RING_MAGIC);
+            write_u64(&mut mmap, RECORD_SIZE_OFFSET, T::size() as u64);
+            write_u64(&mut mmap, CAPACITY_OFFSET, capacity as u64);
+            write_u64(&mut mmap, READ_AT_OFFSET, 0);
+            write_u64(&mut mmap, LENGTH_OFFSET, 0);
+            (0, 0)
+        };
+
+        Ok(Self {
+            read_at,
+            len,
+            cap: capacity,
+            header: HEADER_SIZE,
+            memory: Arc::new(mmap),
+            file: Some(file),
+            phantom: PhantomData,
+        })
+    }
+
+    /// Exclusive access to the backing mapping, used by every in-place mutator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a [`RingBytes`] view returned by [`Ring::freeze_range`] is still
+    /// alive: letting a write proceed could overwrite bytes that view promises
+    /// are frozen. [`Ring::reserve`]/[`Ring::shrink_to`] remap into a brand new
+    /// `Arc` instead of writing through this one, so they're unaffected.
+    fn memory_mut(&mut self) -> &mut MmapMut {
+        Arc::get_mut(&mut self.memory)
+            .expect("Ring mutated while a RingBytes view returned by freeze_range is outstanding")
+    }
+
+    /// Persist the current `read_at`/`len` into the header, if this Ring is
+    /// file-backed; a no-op for [`Ring::with_capacity`].
+    fn sync_header(&mut self) {
+        if self.header > 0 {
+            let read_at = self.read_at as u64;
+            let len = self.len as u64;
+            let memory = self.memory_mut();
+            write_u64(memory, READ_AT_OFFSET, read_at);
+            write_u64(memory, LENGTH_OFFSET, len);
+        }
+    }
+
+    /// Block until every record and the header written so far are durably on disk.
+    ///
+    /// A no-op for [`Ring::with_capacity`], which has no backing file.
+    pub fn flush(&self) -> io::Result<()> {
+        self.memory.flush()
+    }
+
+    /// Block until the byte range covering logical records `[start, end)` is
+    /// durably on disk, without waiting on the rest of the mapping.
+    ///
+    /// # Invariants
+    ///
+    /// * `start <= end <= self.len()`
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - Logical index, inclusive, of the first record to flush.
+    /// * `end` - Logical index, exclusive, of the last record to flush.
+    pub fn flush_range(&self, start: usize, end: usize) -> io::Result<()> {
+        if start == end {
+            return Ok(());
+        }
+
+        // The logical range may straddle the end of the physical buffer, same
+        // as a write in `extend_from_slice`, so flush it in up to two runs.
+        let physical_start = (self.read_at + start) % self.cap;
+        let count = end - start;
+
+        if physical_start + count <= self.cap {
+            self.memory
+                .flush_range(self.header + physical_start * T::size(), count * T::size())
+        } else {
+            let first_count = self.cap - physical_start;
+            self.memory
+                .flush_range(self.header + physical_start * T::size(), first_count * T::size())?;
+            self.memory
+                .flush_range(self.header, (count - first_count) * T::size())
         }
     }
 
@@ -66,9 +251,10 @@ impl<T: Record> Ring<T> {
             return;
         }
 
-        // We need to left shift some bytes.
-        self.memory.copy_within((len * T::size()).., 0);
+        // No copy needed, just advance past the trimmed records.
+        self.read_at = (self.read_at + len) % self.cap;
         self.len -= len;
+        self.sync_header();
     }
 
     #[inline]
@@ -79,11 +265,13 @@ impl<T: Record> Ring<T> {
         }
 
         // Copy record bytes to internal buffers.
-        let offset = self.len * T::size();
+        let tail = (self.read_at + self.len) % self.cap;
+        let offset = self.header + tail * T::size();
         let src = T::to_bytes(record.borrow());
-        let dst = &mut self.memory[offset..(offset + src.len())];
+        let dst = &mut self.memory_mut()[offset..(offset + src.len())];
         dst.copy_from_slice(src);
         self.len += 1;
+        self.sync_header();
 
         // Indicate that record was accepted.
         true
@@ -102,12 +290,25 @@ impl<T: Record> Ring<T> {
             return to_reject;
         }
 
-        // Copy record bytes to internal buffers.
-        let offset = self.len * T::size();
-        let src = T::to_bytes_slice(to_append);
-        let dst = &mut self.memory[offset..(offset + src.len())];
-        dst.copy_from_slice(src);
+        // The write may have to wrap around the end of the buffer, so split it at
+        // the wrap boundary and copy each run separately.
+        let tail = (self.read_at + self.len) % self.cap;
+        let first_len = min(to_append.len(), self.cap - tail);
+        let (first, second) = to_append.split_at(first_len);
+
+        let header = self.header;
+        let offset = header + tail * T::size();
+        let first_bytes = T::to_bytes_slice(first);
+        let second_bytes = (!second.is_empty()).then(|| T::to_bytes_slice(second));
+
+        let memory = self.memory_mut();
+        memory[offset..(offset + first_bytes.len())].copy_from_slice(first_bytes);
+        if let Some(second_bytes) = second_bytes {
+            memory[header..(header + second_bytes.len())].copy_from_slice(second_bytes);
+        }
+
         self.len += to_append.len();
+        self.sync_header();
 
         // Return all the rejected records.
         to_reject
@@ -115,17 +316,490 @@ impl<T: Record> Ring<T> {
 
     #[inline]
     pub fn clear(&mut self) {
+        self.read_at = 0;
         self.len = 0;
+        self.sync_header();
     }
+
+    /// Return the two contiguous runs that together make up all records in this Ring.
+    ///
+    /// The second slice is empty unless the live records wrap around the end of the
+    /// buffer, in which case it holds the wrapped-around remainder.
+    #[inline]
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        if self.len == 0 {
+            return (&[], &[]);
+        }
+
+        if self.read_at + self.len <= self.cap {
+            let start = self.header + self.read_at * T::size();
+            let end = self.header + (self.read_at + self.len) * T::size();
+            (T::from_bytes_slice(&self.memory[start..end]), &[])
+        } else {
+            let wrapped = self.read_at + self.len - self.cap;
+            let start = self.header + self.read_at * T::size();
+            let end = self.header + self.cap * T::size();
+            let first = T::from_bytes_slice(&self.memory[start..end]);
+            let second = T::from_bytes_slice(&self.memory[self.header..self.header + wrapped * T::size()]);
+            (first, second)
+        }
+    }
+
+    /// Snapshot of how full this Ring is and how much room it has.
+    pub fn limits(&self) -> RingLimits {
+        RingLimits {
+            len: self.len,
+            capacity: self.cap,
+            bytes_used: self.len * T::size(),
+            bytes_capacity: self.cap * T::size(),
+        }
+    }
+
+    /// Grow usable capacity by `additional` records, without dropping any live records.
+    ///
+    /// # Arguments
+    ///
+    /// * `additional` - Number of additional records of capacity to add.
+    pub fn reserve(&mut self, additional: usize) {
+        self.resize(self.cap + additional);
+    }
+
+    /// Shrink capacity down towards `min_capacity`, without dropping any live records.
+    ///
+    /// Refuses to drop capacity below [`Ring::len`]; if `min_capacity` is already
+    /// `>= self.capacity()`, this is a no-op.
+    ///
+    /// If this Ring is file-backed and a [`RingBytes`] view returned by
+    /// [`Ring::freeze_range`] is still outstanding, the in-memory capacity still
+    /// shrinks, but truncating the backing file is deferred until a future call
+    /// made once every outstanding view has been dropped -- truncating now would
+    /// invalidate pages that view's old mapping still points at.
+    ///
+    /// # Arguments
+    ///
+    /// * `min_capacity` - Capacity to shrink towards.
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        let min_capacity = min_capacity.max(self.len);
+        if min_capacity < self.cap {
+            self.resize(min_capacity);
+        }
+    }
+
+    /// Swap in a new mapping sized for `new_capacity` records, copying every live
+    /// record across in logical order so survivors land contiguously starting right
+    /// after the header, and resizing the backing file if this Ring was opened via
+    /// [`Ring::open`].
+    ///
+    /// Growing the file happens before survivors are copied, since the new mapping
+    /// needs the backing big enough to hold them; shrinking happens only after, since
+    /// survivors past the new length may still live at their old physical offsets in
+    /// `self.memory` and truncating first would invalidate those pages out from under
+    /// the read (SIGBUS).
+    ///
+    /// This always allocates a fresh `Arc<MmapMut>` rather than writing through the
+    /// old one, so any [`RingBytes`] view returned by [`Ring::freeze_range`] before
+    /// the call keeps referencing the old (untouched, still valid) mapping; resizing
+    /// never has to refuse to run just because a view happens to be outstanding.
+    ///
+    /// Truncating the backing file is different, though: the file underlies both
+    /// the old mapping and the new one, so shrinking it invalidates pages in *any*
+    /// mapping of it past the new length, including the old one a [`RingBytes`]
+    /// still points at. So if a view is outstanding, the file is left at its
+    /// current (larger) size -- only the in-memory capacity shrinks -- and the
+    /// actual truncation is deferred to a future `reserve`/`shrink_to` call made
+    /// once every outstanding view has been dropped.
+    fn resize(&mut self, new_capacity: usize) {
+        let total_len = self.header + new_capacity * T::size();
+
+        // Captured before `self.memory` is replaced below: reflects whether a
+        // `RingBytes` view still holds a clone of the *old* mapping's `Arc`.
+        let view_outstanding = Arc::strong_count(&self.memory) > 1;
+
+        let mut memory = match &self.file {
+            Some(file) => {
+                let current_len = file.metadata().expect("Cannot stat backing file for Ring").len();
+                if total_len as u64 > current_len {
+                    file.set_len(total_len as u64)
+                        .expect("Cannot grow backing file for Ring");
+                }
+
+                // Safety: `file` stays open for the lifetime of the new mapping; it's
+                // at least `total_len` bytes long, whether or not it's been truncated
+                // down to exactly that length yet.
+                unsafe {
+                    MmapOptions::new()
+                        .len(total_len)
+                        .map_mut(file)
+                        .expect("Cannot remap backing file for Ring")
+                }
+            }
+            None => MmapOptions::new()
+                .len(total_len)
+                .map_anon()
+                .expect("Cannot allocate memory to resize Ring"),
+        };
+
+        if self.header > 0 {
+            memory[..self.header].copy_from_slice(&self.memory[..self.header]);
+        }
+
+        let (first, second) = self.as_slices();
+        let first_bytes = T::to_bytes_slice(first);
+        memory[self.header..self.header + first_bytes.len()].copy_from_slice(first_bytes);
+
+        if !second.is_empty() {
+            let second_bytes = T::to_bytes_slice(second);
+            let offset = self.header + first_bytes.len();
+            memory[offset..offset + second_bytes.len()].copy_from_slice(second_bytes);
+        }
+
+        self.memory = Arc::new(memory);
+        self.cap = new_capacity;
+        self.read_at = 0;
+
+        if self.header > 0 {
+            let cap = self.cap as u64;
+            write_u64(self.memory_mut(), CAPACITY_OFFSET, cap);
+        }
+        self.sync_header();
+
+        // Every survivor has now been copied into the new mapping, so it's safe to
+        // drop the rest of the file, if this is a shrink -- unless an outstanding
+        // `RingBytes` view still points at the old mapping of this same file, in
+        // which case truncating now would invalidate its pages out from under it.
+        if let Some(file) = &self.file {
+            if !view_outstanding {
+                file.set_len(total_len as u64)
+                    .expect("Cannot shrink backing file for Ring");
+            }
+        }
+    }
+
+    /// Return a reference-counted, zero-copy view over the raw bytes backing
+    /// records `[start, end)`, in the style of the `bytes` crate's `Bytes`/
+    /// `slice_ref`, so callers can hand them downstream without copying.
+    ///
+    /// The returned [`RingBytes`] clones this Ring's `Arc<MmapMut>`, which keeps
+    /// the mapping alive even past this Ring's own lifetime, and -- because every
+    /// in-place mutator goes through [`Ring::memory_mut`], which needs exclusive
+    /// access to that `Arc` -- pins the frozen bytes against being overwritten by
+    /// [`Ring::push`]/[`Ring::extend_from_slice`]/[`Ring::trim`]/[`Ring::clear`]
+    /// for as long as the view is outstanding. [`Ring::reserve`]/[`Ring::shrink_to`]
+    /// remap into a new `Arc` instead, so they remain unaffected either way.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start > end`, `end > self.len()`, or the requested range wraps
+    /// around the end of the buffer; use [`Ring::as_slices`] to find the wrap
+    /// boundary and request a sub-range within one of its two runs instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - Logical index, inclusive, of the first record in the view.
+    /// * `end` - Logical index, exclusive, of the last record in the view.
+    pub fn freeze_range(&self, start: usize, end: usize) -> RingBytes<T> {
+        assert!(start <= end && end <= self.len, "Ring::freeze_range() range out of bounds");
+
+        let physical_start = (self.read_at + start) % self.cap;
+        let count = end - start;
+
+        assert!(
+            physical_start + count <= self.cap,
+            "Ring::freeze_range() cannot span a wrapped region; request a sub-range within \
+             as_slices()'s first or second run instead"
+        );
+
+        let byte_start = self.header + physical_start * T::size();
+        let byte_end = byte_start + count * T::size();
+
+        RingBytes {
+            memory: self.memory.clone(),
+            range: byte_start..byte_end,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// A reference-counted, zero-copy view over a contiguous byte range of a
+/// [`Ring`], returned by [`Ring::freeze_range`]; akin to a `bytes::Bytes` slice.
+///
+/// Cloning the [`Ring`]'s `Arc<MmapMut>` rather than copying its bytes keeps the
+/// mapping alive for as long as this view (or a clone of it) is, and pins it
+/// against in-place mutation -- see [`Ring::freeze_range`] for the exact invariant.
+#[derive(Debug, Clone)]
+pub struct RingBytes<T> {
+    memory: Arc<MmapMut>,
+    range: Range<usize>,
+    phantom: PhantomData<T>,
+}
+
+impl<T: Record> RingBytes<T> {
+    /// View the frozen range as typed records instead of raw bytes.
+    pub fn as_records(&self) -> &[T] {
+        T::from_bytes_slice(&self.memory[self.range.clone()])
+    }
+}
+
+impl<T> Deref for RingBytes<T> {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.memory[self.range.clone()]
+    }
+}
+
+/// Read a little-endian `u64` header field starting at `offset`.
+fn read_u64(mem: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(mem[offset..offset + 8].try_into().unwrap())
+}
+
+/// Write a little-endian `u64` header field starting at `offset`.
+fn write_u64(mem: &mut [u8], offset: usize, value: u64) {
+    mem[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
 }
 
 impl<T: Record> Deref for Ring<T> {
     type Target = [T];
 
+    /// # Panics
+    ///
+    /// Panics once the live records have wrapped around the end of the buffer;
+    /// use [`Ring::as_slices`] instead in that case.
     #[inline]
     fn deref(&self) -> &Self::Target {
-        let end = self.len * T::size();
-        T::from_bytes_slice(&self.memory[..end])
+        let (first, second) = self.as_slices();
+        assert!(
+            second.is_empty(),
+            "Ring::deref() cannot be used once records have wrapped; use as_slices() instead"
+        );
+        first
+    }
+}
+
+impl<T: Record> Ring<T> {
+    /// Split this Ring into a [`Producer`]/[`Consumer`] pair sharing the same
+    /// backing mapping, so one thread can append while another queries without
+    /// any external locking.
+    ///
+    /// Follows the heapless `spsc::Queue` convention of sacrificing one physical
+    /// slot, so `head == tail` unambiguously means empty and no separate length
+    /// counter needs to be kept in sync between the two sides; usable capacity
+    /// after splitting is [`Ring::capacity`]` - 1`.
+    ///
+    /// # Invariants
+    ///
+    /// * `self.len() < self.capacity()`, i.e. this Ring must have at least one
+    ///   free slot before splitting.
+    /// * No [`RingBytes`] view returned by [`Ring::freeze_range`] may still be
+    ///   outstanding, since splitting needs to take the mapping out of its `Arc`.
+    ///
+    /// # Note
+    ///
+    /// If this Ring was opened via [`Ring::open`], the backing file is closed
+    /// (the mapping itself stays valid); [`Ring::flush`]/[`Ring::reserve`] have
+    /// no equivalent on [`Producer`]/[`Consumer`].
+    pub fn split(self) -> (Producer<T>, Consumer<T>) {
+        assert!(
+            self.len < self.cap,
+            "Ring::split requires at least one free slot; sacrificing a slot for head/tail needs it"
+        );
+
+        let head = (self.read_at + self.len) % self.cap;
+        let tail = self.read_at;
+
+        let memory = Arc::try_unwrap(self.memory).unwrap_or_else(|_| {
+            panic!("Ring::split called while a RingBytes view returned by freeze_range is outstanding")
+        });
+
+        let shared = Arc::new(Shared {
+            memory: UnsafeCell::new(memory),
+            header: self.header,
+            cap: self.cap,
+            head: AtomicUsize::new(head),
+            tail: AtomicUsize::new(tail),
+            phantom: PhantomData,
+        });
+
+        (
+            Producer { shared: shared.clone() },
+            Consumer { shared },
+        )
+    }
+}
+
+/// Mapping and head/tail indices shared between a [`Producer`]/[`Consumer`] pair.
+struct Shared<T> {
+    memory: UnsafeCell<MmapMut>,
+    header: usize,
+    /// Physical capacity, one slot larger than what's ever actually usable; see
+    /// [`Ring::split`].
+    cap: usize,
+    /// Producer-owned next write index.
+    head: AtomicUsize,
+    /// Consumer-owned next read index.
+    tail: AtomicUsize,
+    phantom: PhantomData<T>,
+}
+
+// Safety: `Producer` only ever writes to `[tail, head)`'s complement (the free
+// region) and publishes via a Release store to `head`; `Consumer` only ever reads
+// `[tail, head)` (established via an Acquire load of `head`) and only ever writes
+// `tail` itself. The two index ranges never overlap, so concurrent access from
+// both sides is sound despite `MmapMut` not being `Sync` on its own.
+unsafe impl<T: Send> Sync for Shared<T> {}
+
+/// Producer half of a [`Ring`] split via [`Ring::split`]; only ever appends.
+pub struct Producer<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// Consumer half of a [`Ring`] split via [`Ring::split`]; only ever trims.
+pub struct Consumer<T> {
+    shared: Arc<Shared<T>>,
+}
+
+// `Producer`/`Consumer` are `Send` automatically: each only holds an `Arc<Shared<T>>`,
+// which is `Send` whenever `Shared<T>` is `Send + Sync`, and the `unsafe impl Sync`
+// above is exactly what makes that hold despite the `UnsafeCell<MmapMut>` inside.
+
+impl<T: Record> Producer<T> {
+    /// Append a single record, returning `false` if there was no free slot for it.
+    pub fn push(&self, record: T) -> bool {
+        self.push_slice(std::slice::from_ref(&record)).is_empty()
+    }
+
+    /// Append as many records as there's free space for, returning whatever
+    /// didn't fit, same convention as [`Ring::extend_from_slice`].
+    pub fn push_slice<'a>(&self, records: &'a [T]) -> &'a [T] {
+        let shared = &*self.shared;
+
+        // Producer-owned; only this side ever advances `head`.
+        let head = shared.head.load(Ordering::Relaxed);
+        // Acquire pairs with the consumer's Release store in `Consumer::trim`, so
+        // this observes every slot the consumer has freed up so far.
+        let tail = shared.tail.load(Ordering::Acquire);
+
+        let live = (head + shared.cap - tail) % shared.cap;
+        let free = shared.cap - 1 - live;
+
+        let index = min(records.len(), free);
+        let (to_append, to_reject) = records.split_at(index);
+
+        if !to_append.is_empty() {
+            let first_len = min(to_append.len(), shared.cap - head);
+            let (first, second) = to_append.split_at(first_len);
+
+            // Safety: the slots starting at `head` for up to `free` records are
+            // exclusively ours to write -- the consumer never reads ahead of
+            // `head`, and the Acquire load of `tail` above confirms they're free.
+            unsafe {
+                let memory = &mut *shared.memory.get();
+
+                let offset = shared.header + head * T::size();
+                let src = T::to_bytes_slice(first);
+                memory[offset..offset + src.len()].copy_from_slice(src);
+
+                if !second.is_empty() {
+                    let src = T::to_bytes_slice(second);
+                    memory[shared.header..shared.header + src.len()].copy_from_slice(src);
+                }
+            }
+
+            // Release so a consumer's Acquire load of `head` can't observe the
+            // new write index before every byte above has actually landed.
+            let new_head = (head + to_append.len()) % shared.cap;
+            shared.head.store(new_head, Ordering::Release);
+        }
+
+        to_reject
+    }
+}
+
+impl<T: Record> Consumer<T> {
+    /// Number of records currently available to read.
+    pub fn len(&self) -> usize {
+        let shared = &*self.shared;
+        let head = shared.head.load(Ordering::Acquire);
+        let tail = shared.tail.load(Ordering::Relaxed);
+        (head + shared.cap - tail) % shared.cap
+    }
+
+    /// true if there are no records available to read right now.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the two contiguous runs that together make up all records
+    /// currently available to read, same convention as [`Ring::as_slices`].
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let shared = &*self.shared;
+
+        // Acquire pairs with the producer's Release store in `Producer::push_slice`.
+        let head = shared.head.load(Ordering::Acquire);
+        // Consumer-owned; only this side ever advances `tail`.
+        let tail = shared.tail.load(Ordering::Relaxed);
+
+        let live = (head + shared.cap - tail) % shared.cap;
+        if live == 0 {
+            return (&[], &[]);
+        }
+
+        // Safety: the Acquire load of `head` above happens-after the producer's
+        // Release store, so every byte in `[tail, tail + live)` has landed; the
+        // producer never writes behind `tail`.
+        unsafe {
+            let memory = &*shared.memory.get();
+
+            if tail + live <= shared.cap {
+                let start = shared.header + tail * T::size();
+                let end = shared.header + (tail + live) * T::size();
+                (T::from_bytes_slice(&memory[start..end]), &[])
+            } else {
+                let wrapped = tail + live - shared.cap;
+                let start = shared.header + tail * T::size();
+                let end = shared.header + shared.cap * T::size();
+                let first = T::from_bytes_slice(&memory[start..end]);
+                let second = T::from_bytes_slice(&memory[shared.header..shared.header + wrapped * T::size()]);
+                (first, second)
+            }
+        }
+    }
+
+    /// Trim `len` records from the front of what's readable.
+    ///
+    /// # Invariants
+    ///
+    /// * `len <= self.len()`
+    pub fn trim(&mut self, len: usize) {
+        if len == 0 {
+            return;
+        }
+
+        let shared = &*self.shared;
+        let tail = shared.tail.load(Ordering::Relaxed);
+        let new_tail = (tail + len) % shared.cap;
+
+        // Release so the producer's Acquire load of `tail` observes these slots
+        // as free only after we're done reading their old contents.
+        shared.tail.store(new_tail, Ordering::Release);
+    }
+}
+
+impl<T: Record> Deref for Consumer<T> {
+    type Target = [T];
+
+    /// # Panics
+    ///
+    /// Panics once the readable records have wrapped around the end of the
+    /// buffer; use [`Consumer::as_slices`] instead in that case.
+    fn deref(&self) -> &Self::Target {
+        let (first, second) = self.as_slices();
+        assert!(
+            second.is_empty(),
+            "Consumer::deref() cannot be used once records have wrapped; use as_slices() instead"
+        );
+        first
     }
 }
 
@@ -147,7 +821,7 @@ mod tests {
 
         fn test_extend_slice(&mut self, records: &[T]);
 
-        fn test_records(&self) -> &[T];
+        fn test_records(&self) -> Vec<T>;
     }
 
     // Reference implementation of ring buffer using a Vec.
@@ -181,8 +855,8 @@ mod tests {
             self.extend_from_slice(records);
         }
 
-        fn test_records(&self) -> &[T] {
-            &self
+        fn test_records(&self) -> Vec<T> {
+            self.clone()
         }
     }
 
@@ -217,8 +891,11 @@ mod tests {
             self.extend_from_slice(records);
         }
 
-        fn test_records(&self) -> &[T] {
-            &self
+        fn test_records(&self) -> Vec<T> {
+            // Records may straddle the end of the buffer, so flatten both runs
+            // into an owned Vec to compare against the reference implementation.
+            let (first, second) = self.as_slices();
+            first.iter().chain(second).copied().collect()
         }
     }
 
@@ -287,4 +964,149 @@ mod tests {
     // FIXME: We need Eq for testing for equality.
     // state_machine_test!(state_machine_f32, OperationF32, f32);
     // state_machine_test!(state_machine_f64, OperationF64, f64);
+
+    /// Concurrently drives a real producer thread and a real consumer thread
+    /// across randomly sized batches, checking that the records the consumer
+    /// observes are exactly the ones pushed, in order -- the kind of test meant
+    /// to be run under a thread sanitizer to catch a broken Acquire/Release pairing.
+    #[test]
+    fn test_spsc_split_preserves_fifo_order() {
+        check!().with_type::<Vec<u8>>().for_each(|batch_sizes| {
+            let total: usize = batch_sizes.iter().map(|&n| n as usize).sum();
+            let expected: Vec<usize> = (0..total).collect();
+            let batch_sizes = batch_sizes.clone();
+
+            let ring = Ring::<usize>::with_capacity(RING_CAPACITY);
+            let (producer, mut consumer) = ring.split();
+
+            let producer_thread = std::thread::spawn(move || {
+                let mut next = 0usize;
+                for batch_size in batch_sizes {
+                    let records: Vec<usize> = (next..next + batch_size as usize).collect();
+                    next += records.len();
+
+                    let mut offset = 0;
+                    while offset < records.len() {
+                        let rejected = producer.push_slice(&records[offset..]);
+                        offset = records.len() - rejected.len();
+                    }
+                }
+            });
+
+            let mut received = Vec::with_capacity(total);
+            while received.len() < total {
+                let (first, second) = consumer.as_slices();
+                received.extend_from_slice(first);
+                received.extend_from_slice(second);
+
+                let drained = first.len() + second.len();
+                if drained > 0 {
+                    consumer.trim(drained);
+                }
+            }
+
+            producer_thread.join().expect("Producer thread panicked");
+            assert_eq!(received, expected);
+        });
+    }
+
+    #[test]
+    fn test_freeze_range_reads_live_records() {
+        let mut ring = Ring::<u32>::with_capacity(4);
+        ring.extend_from_slice(&[1, 2, 3]);
+
+        let view = ring.freeze_range(1, 3);
+        assert_eq!(view.as_records(), &[2, 3]);
+        assert_eq!(&*view, u32::to_bytes_slice(&[2u32, 3]));
+
+        // The Arc keeps the frozen bytes readable even once the Ring that
+        // created the view is gone.
+        drop(ring);
+        assert_eq!(view.as_records(), &[2, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "outstanding")]
+    fn test_freeze_range_pins_against_mutation() {
+        let mut ring = Ring::<u32>::with_capacity(4);
+        ring.extend_from_slice(&[1, 2, 3]);
+
+        let view = ring.freeze_range(0, 2);
+        ring.push(4);
+        drop(view);
+    }
+
+    #[test]
+    fn test_resize_leaves_outstanding_views_untouched() {
+        let mut ring = Ring::<u32>::with_capacity(2);
+        ring.extend_from_slice(&[1, 2]);
+
+        let view = ring.freeze_range(0, 2);
+        ring.reserve(2);
+        ring.push(3);
+
+        assert_eq!(view.as_records(), &[1, 2]);
+        assert_eq!(ring.as_slices(), (&[1, 2, 3][..], &[][..]));
+    }
+
+    /// Unique path for a file-backed Ring, cleaned up by the caller once done with it.
+    fn ring_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("crosstream-ring-test-ring-{name}-{}", std::process::id()))
+    }
+
+    #[test]
+    fn test_file_backed_shrink_to_after_wrap_preserves_records() {
+        let path = ring_path("shrink-after-wrap");
+        let mut ring = Ring::<u32>::open(&path, 6).unwrap();
+
+        // Fill, trim, then push again so the live records wrap around the end
+        // of the buffer (and no longer start at physical offset 0), while still
+        // leaving free capacity above `len`.
+        ring.extend_from_slice(&[1, 2, 3, 4]);
+        ring.trim(2);
+        ring.extend_from_slice(&[5, 6, 7]);
+        assert_eq!(ring.as_slices(), (&[3, 4, 5, 6][..], &[7][..]));
+
+        // Shrinking towards the live length forces `resize` to truncate the
+        // backing file; the wrapped survivors must be copied into the new
+        // mapping before that truncation happens, or this reads/writes past
+        // the (already shrunk) file.
+        ring.shrink_to(5);
+        assert_eq!(ring.capacity(), 5);
+        assert_eq!(ring.as_slices(), (&[3, 4, 5, 6, 7][..], &[][..]));
+
+        drop(ring);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_file_backed_shrink_to_defers_truncate_while_view_outstanding() {
+        let path = ring_path("shrink-with-view");
+        let mut ring = Ring::<u32>::open(&path, 6).unwrap();
+        ring.extend_from_slice(&[1, 2, 3, 4]);
+
+        let file_len_before = std::fs::metadata(&path).unwrap().len();
+
+        // Shrinking while a view is outstanding must not truncate the file out
+        // from under the old mapping it still points at; the in-memory capacity
+        // still shrinks, but the file stays at its current size.
+        let view = ring.freeze_range(0, 4);
+        ring.shrink_to(5);
+        assert_eq!(ring.capacity(), 5);
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), file_len_before);
+
+        // The view is still readable -- no SIGBUS from a file truncated out from
+        // under its mapping -- and the Ring itself is unaffected.
+        assert_eq!(view.as_records(), &[1, 2, 3, 4]);
+        assert_eq!(ring.as_slices(), (&[1, 2, 3, 4][..], &[][..]));
+
+        // Once the view is gone, a later shrink actually truncates the file.
+        drop(view);
+        ring.shrink_to(4);
+        assert_eq!(ring.capacity(), 4);
+        assert!(std::fs::metadata(&path).unwrap().len() < file_len_before);
+
+        drop(ring);
+        let _ = std::fs::remove_file(&path);
+    }
 }
@@ -1,18 +1,51 @@
 //! Definition of a container of contiguous elements.
 
+#[cfg(feature = "mmap")]
+use crate::{OffHeap, OffHeapStorage};
+#[cfg(all(feature = "mmap", target_os = "linux"))]
+use crate::MagicStorage;
 use crate::{
-    MemStorage, OffHeap, OffHeapStorage, OnHeap, OnHeapStorage, Record, Storage, VecStorage,
+    InlineStorage, MemStorage, OnHeapStorage, Record, RingStorage, Storage, TryReserveError,
+    VecStorage, cold::Cold, crc::Crc64,
 };
-use std::cmp::min;
+use allocator_api2::alloc::{Allocator, Global};
+use std::{cmp::min, mem::ManuallyDrop, path::PathBuf};
 
 /// Type alias for a [`Segment`] backed by [`VecStorage`].
 pub type VecSegment<T> = Segment<VecStorage<T>>;
 
 /// Type alias for a [`Segment`] backed by [`OffHeapStorage`].
+#[cfg(feature = "mmap")]
 pub type OffHeapSegment<T> = Segment<OffHeapStorage<T>>;
 
 /// Type alias for a [`Segment`] backed by [`OnHeapStorage`].
-pub type OnHeapSegment<T> = Segment<OnHeapStorage<T>>;
+///
+/// Defaults to the [`Global`] allocator; use the `_in` constructors to back
+/// this Segment with a custom [`Allocator`] instead, e.g. a bump arena or a
+/// per-thread pool.
+pub type OnHeapSegment<T, A = Global> = Segment<OnHeapStorage<T, A>>;
+
+/// Type alias for a [`Segment`] backed by [`InlineStorage`].
+///
+/// `N` is fixed at compile time, so this never allocates; good fit for many
+/// small, short-lived segments where a heap/off-heap allocation per segment
+/// would dominate the cost.
+pub type InlineSegment<T, const N: usize> = Segment<InlineStorage<T, N>>;
+
+/// Type alias for a [`Segment`] backed by [`RingStorage`].
+///
+/// Trims in O(1) by advancing a `head` index modulo capacity instead of
+/// left-shifting survivors; records may wrap, so use [`Segment::as_slices`]
+/// rather than [`Segment::records`] to read them.
+pub type RingSegment<T> = Segment<RingStorage<T>>;
+
+/// Type alias for a [`Segment`] backed by [`MagicStorage`].
+///
+/// Trims in O(1) like [`RingSegment`], but thanks to the double-mapped memory
+/// behind [`MagicStorage`], records never need splitting across the wrap, so
+/// [`Segment::records`] stays usable even after the buffer has wrapped.
+#[cfg(all(feature = "mmap", target_os = "linux"))]
+pub type MagicSegment<T> = Segment<MagicStorage<T>>;
 
 /// Segment is a container of contiguous elements.
 ///
@@ -24,11 +57,14 @@ pub type OnHeapSegment<T> = Segment<OnHeapStorage<T>>;
 /// * There can be no gaps between elements, push/remove from back, but only remove from front.
 /// * As of now (and probably forever) only supports elements that implement [`Record`].
 /// * Performance of Segment operations is virtually identical to that of [`Vec`].
-/// * Supports few different types of storage engines; [`VecSegment`], [`OffHeapSegment`] and [`OnHeapSegment`].
+/// * Supports few different types of storage engines; [`VecSegment`], [`OffHeapSegment`], [`OnHeapSegment`], [`InlineSegment`], [`RingSegment`] and [`MagicSegment`].
 #[derive(Debug)]
 pub struct Segment<S: Storage> {
     storage: S,
     trimmer: Trimmer,
+    integrity: Option<Crc64>,
+    cold: Option<Cold>,
+    evicted: usize,
 }
 
 impl<T: Record + Copy> VecSegment<T> {
@@ -39,13 +75,33 @@ impl<T: Record + Copy> VecSegment<T> {
     /// * `capacity` - Maximum number of elements this segment can accommodate.
     /// * `trimmer` - Trimmer to use when appending records into segment.
     pub fn with_capacity(capacity: usize, trimmer: Trimmer) -> VecSegment<T> {
-        Self {
+        Self::try_with_capacity(capacity, trimmer)
+            .expect("Cannot allocate capacity for VecSegment")
+    }
+
+    /// Fallible variant of [`VecSegment::with_capacity`] that returns a
+    /// [`TryReserveError`] instead of aborting when the requested capacity
+    /// could not be allocated.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum number of elements this segment can accommodate.
+    /// * `trimmer` - Trimmer to use when appending records into segment.
+    pub fn try_with_capacity(
+        capacity: usize,
+        trimmer: Trimmer,
+    ) -> Result<VecSegment<T>, TryReserveError> {
+        Ok(Self {
             trimmer,
-            storage: VecStorage::new(capacity),
-        }
+            integrity: None,
+            cold: None,
+            evicted: 0,
+            storage: VecStorage::try_new(capacity)?,
+        })
     }
 }
 
+#[cfg(feature = "mmap")]
 impl<T: Record> OffHeapSegment<T> {
     /// Create a new instance of Segment using memory allocated off-heap.
     ///
@@ -54,15 +110,35 @@ impl<T: Record> OffHeapSegment<T> {
     /// * `capacity` - Maximum number of elements this segment can accommodate.
     /// * `trimmer` - Trimmer to use when appending records into segment.
     pub fn with_capacity(capacity: usize, trimmer: Trimmer) -> OffHeapSegment<T> {
-        Self {
+        Self::try_with_capacity(capacity, trimmer)
+            .expect("Cannot allocate capacity for OffHeapSegment")
+    }
+
+    /// Fallible variant of [`OffHeapSegment::with_capacity`] that returns a
+    /// [`TryReserveError`] instead of aborting when the requested capacity
+    /// could not be allocated.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum number of elements this segment can accommodate.
+    /// * `trimmer` - Trimmer to use when appending records into segment.
+    pub fn try_with_capacity(
+        capacity: usize,
+        trimmer: Trimmer,
+    ) -> Result<OffHeapSegment<T>, TryReserveError> {
+        Ok(Self {
             trimmer,
-            storage: MemStorage::<_, OffHeap>::new(capacity),
-        }
+            integrity: None,
+            cold: None,
+            evicted: 0,
+            storage: MemStorage::<_, OffHeap>::try_new(capacity)?,
+        })
     }
 }
 
 impl<T: Record> OnHeapSegment<T> {
-    /// Create a new instance of Segment using memory allocated on heap.
+    /// Create a new instance of Segment using memory allocated on heap,
+    /// using the [`Global`] allocator.
     ///
     /// * TODO: Add support for huge pages.
     ///
@@ -71,14 +147,163 @@ impl<T: Record> OnHeapSegment<T> {
     /// * `capacity` - Maximum number of elements this segment can accommodate.
     /// * `trimmer` - Trimmer to use when appending records into segment.
     pub fn with_capacity(capacity: usize, trimmer: Trimmer) -> OnHeapSegment<T> {
+        Self::with_capacity_in(capacity, trimmer, Global)
+    }
+
+    /// Fallible variant of [`OnHeapSegment::with_capacity`] that returns a
+    /// [`TryReserveError`] instead of aborting when the requested capacity
+    /// could not be allocated.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum number of elements this segment can accommodate.
+    /// * `trimmer` - Trimmer to use when appending records into segment.
+    pub fn try_with_capacity(
+        capacity: usize,
+        trimmer: Trimmer,
+    ) -> Result<OnHeapSegment<T>, TryReserveError> {
+        Self::try_with_capacity_in(capacity, trimmer, Global)
+    }
+}
+
+impl<T: Record, A: Allocator> OnHeapSegment<T, A> {
+    /// Create a new instance of Segment using memory allocated on heap via `alloc`.
+    ///
+    /// Lets this Segment be backed by a custom [`Allocator`] instead of [`Global`],
+    /// e.g. a bump arena or a per-thread pool, which matters for the ring-buffer use
+    /// case where many segments are cycled and per-allocation syscalls dominate.
+    ///
+    /// * TODO: Add support for huge pages.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum number of elements this segment can accommodate.
+    /// * `trimmer` - Trimmer to use when appending records into segment.
+    /// * `alloc` - Allocator used to request memory for this segment.
+    pub fn with_capacity_in(capacity: usize, trimmer: Trimmer, alloc: A) -> OnHeapSegment<T, A> {
+        Self::try_with_capacity_in(capacity, trimmer, alloc)
+            .expect("Cannot allocate capacity for OnHeapSegment")
+    }
+
+    /// Fallible variant of [`OnHeapSegment::with_capacity_in`] that returns a
+    /// [`TryReserveError`] instead of aborting when the requested capacity
+    /// could not be allocated.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum number of elements this segment can accommodate.
+    /// * `trimmer` - Trimmer to use when appending records into segment.
+    /// * `alloc` - Allocator used to request memory for this segment.
+    pub fn try_with_capacity_in(
+        capacity: usize,
+        trimmer: Trimmer,
+        alloc: A,
+    ) -> Result<OnHeapSegment<T, A>, TryReserveError> {
+        Ok(Self {
+            trimmer,
+            integrity: None,
+            cold: None,
+            evicted: 0,
+            storage: OnHeapStorage::try_new_in(capacity, alloc)?,
+        })
+    }
+}
+
+impl<T: Record + Copy, const N: usize> InlineSegment<T, N> {
+    /// Create a new instance of Segment holding records inline, on the stack.
+    ///
+    /// Unlike the other constructors, this cannot fail; there's no allocation
+    /// to fall short on, capacity `N` is part of the type.
+    ///
+    /// # Arguments
+    ///
+    /// * `trimmer` - Trimmer to use when appending records into segment.
+    pub fn new(trimmer: Trimmer) -> InlineSegment<T, N> {
         Self {
             trimmer,
-            storage: MemStorage::<_, OnHeap>::new(capacity),
+            integrity: None,
+            cold: None,
+            evicted: 0,
+            storage: InlineStorage::new(),
         }
     }
 }
 
-impl<S: Storage> Segment<S> {
+impl<T: Record + Copy> RingSegment<T> {
+    /// Create a new instance of Segment using a circular buffer for memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum number of elements this segment can accommodate.
+    /// * `trimmer` - Trimmer to use when appending records into segment.
+    pub fn with_capacity(capacity: usize, trimmer: Trimmer) -> RingSegment<T> {
+        Self::try_with_capacity(capacity, trimmer)
+            .expect("Cannot allocate capacity for RingSegment")
+    }
+
+    /// Fallible variant of [`RingSegment::with_capacity`] that returns a
+    /// [`TryReserveError`] instead of aborting when the requested capacity
+    /// could not be allocated.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum number of elements this segment can accommodate.
+    /// * `trimmer` - Trimmer to use when appending records into segment.
+    pub fn try_with_capacity(
+        capacity: usize,
+        trimmer: Trimmer,
+    ) -> Result<RingSegment<T>, TryReserveError> {
+        Ok(Self {
+            trimmer,
+            integrity: None,
+            cold: None,
+            evicted: 0,
+            storage: RingStorage::try_new(capacity)?,
+        })
+    }
+}
+
+#[cfg(all(feature = "mmap", target_os = "linux"))]
+impl<T: Record + Copy> MagicSegment<T> {
+    /// Create a new instance of Segment using a double-mapped ring buffer for memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum number of elements this segment can accommodate; rounded
+    ///   up to a whole number of pages, see [`MagicStorage`]'s invariant.
+    /// * `trimmer` - Trimmer to use when appending records into segment.
+    pub fn with_capacity(capacity: usize, trimmer: Trimmer) -> MagicSegment<T> {
+        Self::try_with_capacity(capacity, trimmer)
+            .expect("Cannot double-map memory for MagicSegment")
+    }
+
+    /// Fallible variant of [`MagicSegment::with_capacity`] that returns a
+    /// [`TryReserveError`] instead of aborting when the double mapping could
+    /// not be established.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum number of elements this segment can accommodate; rounded
+    ///   up to a whole number of pages, see [`MagicStorage`]'s invariant.
+    /// * `trimmer` - Trimmer to use when appending records into segment.
+    pub fn try_with_capacity(
+        capacity: usize,
+        trimmer: Trimmer,
+    ) -> Result<MagicSegment<T>, TryReserveError> {
+        Ok(Self {
+            trimmer,
+            integrity: None,
+            cold: None,
+            evicted: 0,
+            storage: MagicStorage::try_new(capacity)?,
+        })
+    }
+}
+
+impl<S: Storage> Segment<S>
+where
+    S::Record: Record,
+{
     /// Number of records currently stored in this Segment.
     pub fn len(&self) -> usize {
         self.storage.length()
@@ -131,6 +356,33 @@ impl<S: Storage> Segment<S> {
 
         // We need to left shift some bytes.
         self.storage.trim(len);
+        self.resync_integrity();
+    }
+
+    /// Remove the first `n` records from this Segment, yielding them by value.
+    ///
+    /// Unlike [`Segment::trim`], which silently discards the removed records, this lets
+    /// callers observe them before they're gone, e.g. to flush them to disk before the
+    /// Segment would otherwise overwrite them.
+    ///
+    /// * If `n > self.len()`, this drains every record currently in the Segment.
+    /// * The left-shift of the surviving records happens exactly once, when the
+    ///   returned [`Drain`] is dropped. See [`Drain::keep_rest`] to stop early.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Number of records to drain from the front of this Segment.
+    pub fn drain(&mut self, n: usize) -> Drain<'_, S>
+    where
+        S::Record: Copy,
+    {
+        let len = min(n, self.len());
+
+        Drain {
+            segment: self,
+            len,
+            index: 0,
+        }
     }
 
     /// Append a record into this Segment.
@@ -144,7 +396,7 @@ impl<S: Storage> Segment<S> {
     pub fn push(&mut self, record: S::Record) -> Option<S::Record> {
         // If we don't have enough capacity, attempt to trim records.
         if self.remaining() == 0 {
-            self.run_trimmer();
+            self.run_trimmer(1);
         }
 
         // If we still don't have enough space, there is nothing else to do.
@@ -154,6 +406,7 @@ impl<S: Storage> Segment<S> {
 
         // Copy record bytes to internal buffers.
         self.storage.extend(&[record]);
+        self.track_append(&[record]);
 
         // The record was consumed, nothing to return.
         None
@@ -170,7 +423,7 @@ impl<S: Storage> Segment<S> {
     pub fn extend_from_slice<'a>(&mut self, records: &'a [S::Record]) -> &'a [S::Record] {
         // If we don't have enough capacity, attempt to trim records.
         if self.remaining() < records.len() {
-            self.run_trimmer();
+            self.run_trimmer(records.len() - self.remaining());
         }
 
         // Safety: index is guaranteed to be <= records.len() due to the conditional check.
@@ -186,6 +439,7 @@ impl<S: Storage> Segment<S> {
 
         // Copy record bytes to internal buffers.
         self.storage.extend(to_append);
+        self.track_append(to_append);
 
         // Return all the rejected records.
         to_reject
@@ -194,30 +448,226 @@ impl<S: Storage> Segment<S> {
     /// Remove all elements from this Segment.
     ///
     /// * This is a constant time O(1) operation.
+    ///
+    /// Records previously spilled by a [`Trimmer::Compress`] are evicted history, not
+    /// live elements, so they remain retrievable through [`Segment::iter`] afterwards.
     pub fn clear(&mut self) {
         self.storage.clear();
+
+        if self.integrity.is_some() {
+            self.integrity = Some(Crc64::new());
+        }
     }
 
     /// Returns reference to all the records in a segment.
+    ///
+    /// # Panics
+    ///
+    /// Panics for storage engines that hold records non-contiguously once they've
+    /// wrapped (i.e. [`RingSegment`]); use [`Segment::as_slices`] instead for those.
     pub fn records(&self) -> &[S::Record] {
         self.storage.records()
     }
 
-    fn run_trimmer(&mut self) {
-        let trim_len = match self.trimmer {
-            Trimmer::None => 0,
-            Trimmer::Trim(len) => len,
+    /// Returns the two contiguous runs that together make up all records in this Segment.
+    ///
+    /// For storage engines where records never wrap (everything except [`RingSegment`])
+    /// the second slice is always empty, so [`Segment::records`] works just as well.
+    pub fn as_slices(&self) -> (&[S::Record], &[S::Record]) {
+        self.storage.records_slices()
+    }
+
+    /// Iterate over every record this Segment has ever held that's still retrievable.
+    ///
+    /// For a Segment using [`Trimmer::Compress`], this first lazily decompresses each
+    /// spilled block, oldest first, then yields the records currently live in the hot
+    /// buffer; for every other [`Trimmer`] it's equivalent to iterating [`Segment::as_slices`].
+    /// Takes `&mut self` because decompressing a spilled block reads from the backing
+    /// spill file.
+    pub fn iter(&mut self) -> Iter<'_, S>
+    where
+        S::Record: Copy,
+    {
+        Iter {
+            segment: self,
+            block: 0,
+            buffered: Vec::new().into_iter(),
+            hot: 0,
+        }
+    }
+
+    /// Collapse runs of consecutive equal records (in logical order) down to the
+    /// first record of each run, shrinking [`Segment::len`] accordingly.
+    ///
+    /// See [`Segment::dedup_by`] for the underlying algorithm and its complexity.
+    pub fn dedup(&mut self)
+    where
+        S::Record: Copy + PartialEq,
+    {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    /// Like [`Segment::dedup`], but uses `same` to decide whether consecutive
+    /// records should be considered equal instead of requiring [`PartialEq`].
+    ///
+    /// Implemented in two phases to stay write-free in the common case where
+    /// nothing needs removing: phase one scans forward comparing each record to
+    /// its predecessor and performs no writes until it finds the first adjacent
+    /// duplicate; if the scan reaches the end without one, this returns immediately.
+    /// Otherwise phase two continues from there with a write cursor, copying down
+    /// every subsequently-kept record and advancing the cursor only when a record
+    /// is retained. For [`RingSegment`], the copy goes through logical indices
+    /// rather than a single contiguous slice, so it stays correct across wraparound.
+    pub fn dedup_by<F>(&mut self, mut same: F)
+    where
+        S::Record: Copy,
+        F: FnMut(&S::Record, &S::Record) -> bool,
+    {
+        let len = self.len();
+        if len < 2 {
+            return;
+        }
+
+        // Phase one: scan for the first adjacent duplicate without writing anything.
+        let first_dup = (1..len).find(|&i| same(&self.storage.get(i - 1), &self.storage.get(i)));
+
+        let Some(first_dup) = first_dup else {
+            return;
         };
 
-        if trim_len > 0 {
-            self.trim(trim_len);
+        // Phase two: continue from there, copying down every subsequently-kept
+        // record and advancing the write cursor only when one is retained.
+        let mut w = first_dup;
+        let mut previous = self.storage.get(w - 1);
+
+        for r in first_dup + 1..len {
+            let current = self.storage.get(r);
+            if !same(&previous, &current) {
+                self.storage.set(w, current);
+                w += 1;
+                previous = current;
+            }
         }
+
+        self.storage.truncate(w);
+        self.resync_integrity();
+    }
+
+    /// Turn on CRC-64 integrity tracking for this Segment.
+    ///
+    /// Once enabled, every [`Segment::push`]/[`Segment::extend_from_slice`] folds the
+    /// newly appended bytes into a running checksum in O(appended bytes), and every
+    /// [`Segment::trim`]/[`Segment::clear`] updates it to match the surviving records.
+    /// Use [`Segment::verify`] to detect corruption of the underlying buffer, e.g. a
+    /// torn write or a stray out-of-bounds write from elsewhere in the process.
+    pub fn enable_integrity(&mut self) {
+        self.integrity = Some(self.compute_checksum());
+    }
+
+    /// The checksum accumulated so far, or `None` if [`Segment::enable_integrity`] hasn't
+    /// been called. Callers can checkpoint this value externally, e.g. alongside a
+    /// persisted [`MmapSegment`](crate::MmapSegment), to detect corruption when reopening it.
+    pub fn checksum(&self) -> Option<u64> {
+        self.integrity.map(Crc64::finalize)
+    }
+
+    /// Recompute the checksum from the records currently in this Segment and compare it
+    /// against the incrementally maintained one.
+    ///
+    /// Returns `true` if integrity tracking isn't enabled, since there's nothing to check.
+    pub fn verify(&self) -> bool {
+        match self.integrity {
+            Some(tracked) => tracked.finalize() == self.compute_checksum().finalize(),
+            None => true,
+        }
+    }
+
+    /// Recompute the checksum from scratch over every record currently in this Segment.
+    fn compute_checksum(&self) -> Crc64 {
+        let (first, second) = self.as_slices();
+
+        let mut crc = Crc64::new();
+        crc.update(S::Record::to_bytes_slice(first));
+        crc.update(S::Record::to_bytes_slice(second));
+        crc
+    }
+
+    /// Fold newly appended records into the running checksum, if tracking is enabled.
+    fn track_append(&mut self, records: &[S::Record]) {
+        if let Some(crc) = &mut self.integrity {
+            crc.update(S::Record::to_bytes_slice(records));
+        }
+    }
+
+    /// Bring the running checksum back in sync after records were removed from the front.
+    fn resync_integrity(&mut self) {
+        if self.integrity.is_some() {
+            self.integrity = Some(self.compute_checksum());
+        }
+    }
+
+    /// Make room for an append that needs `needed` more records of capacity than
+    /// [`Segment::remaining`] currently offers.
+    fn run_trimmer(&mut self, needed: usize) {
+        match &self.trimmer {
+            Trimmer::None => {}
+            Trimmer::Trim(len) => {
+                let len = *len;
+                if len > 0 {
+                    self.trim(len);
+                }
+            }
+            Trimmer::Compress { window, .. } => {
+                let window = *window;
+                let evict = needed.min(self.len());
+                if evict > 0 {
+                    self.compress_trim(evict, window);
+                }
+            }
+        }
+    }
+
+    /// Evict the first `evict` records by LZ77-compressing them into this Segment's
+    /// spill file instead of discarding them, then trim them from the hot buffer.
+    fn compress_trim(&mut self, evict: usize, window: usize) {
+        let bytes = self.front_bytes(evict);
+
+        if self.cold.is_none() {
+            let Trimmer::Compress { spill, .. } = &self.trimmer else {
+                unreachable!("compress_trim is only called under Trimmer::Compress");
+            };
+            self.cold = Some(Cold::open(spill).expect("Cannot open spill file for Segment"));
+        }
+
+        self.cold
+            .as_mut()
+            .unwrap()
+            .spill(self.evicted, &bytes, size_of::<S::Record>(), window)
+            .expect("Cannot write spill block for Segment");
+
+        self.evicted += evict;
+        self.trim(evict);
+    }
+
+    /// Byte representation of the first `n` records in this Segment, in order, copied
+    /// out of whichever one or two physical runs [`Segment::as_slices`] reports.
+    fn front_bytes(&self, n: usize) -> Vec<u8> {
+        let (first, second) = self.as_slices();
+        let mut bytes = Vec::with_capacity(n * size_of::<S::Record>());
+
+        if n <= first.len() {
+            bytes.extend_from_slice(S::Record::to_bytes_slice(&first[..n]));
+        } else {
+            bytes.extend_from_slice(S::Record::to_bytes_slice(first));
+            bytes.extend_from_slice(S::Record::to_bytes_slice(&second[..n - first.len()]));
+        }
+
+        bytes
     }
 }
 
 /// Strategy used to trim records during appends into a [`Segment`].
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[cfg_attr(test, derive(bolero::TypeGenerator))]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Trimmer {
     /// When an append operation occurs and there isn't sufficient capacity
     /// to accommodate records, this does nothing. Meaning one or more records
@@ -228,6 +678,159 @@ pub enum Trimmer {
     /// to accommodate records, this trims first N records from the segment.
     /// However records will  be rejected if N < number of records being appended.
     Trim(usize),
+
+    /// When an append operation occurs and there isn't sufficient capacity to
+    /// accommodate records, this LZ77-compresses exactly as many of the oldest
+    /// records as needed and appends them to `spill` instead of discarding them.
+    /// Spilled records remain readable afterwards through [`Segment::iter`].
+    Compress {
+        /// Append-only file that evicted, compressed records are written to.
+        spill: PathBuf,
+        /// LZ77 match window, in bytes, used to compress each evicted block.
+        window: usize,
+    },
+}
+
+#[cfg(test)]
+impl bolero::TypeGenerator for Trimmer {
+    fn generate<D: bolero::Driver>(driver: &mut D) -> Option<Self> {
+        // `Compress` carries a real filesystem path, which has no meaningful property-test
+        // generator, so only arbitrarily generate the two variants that don't.
+        if bool::generate(driver)? {
+            Some(Trimmer::Trim(usize::generate(driver)?))
+        } else {
+            Some(Trimmer::None)
+        }
+    }
+}
+
+/// Iterator returned by [`Segment::drain`] that removes and yields the first
+/// few records of a [`Segment`].
+///
+/// Dropping a `Drain`, with or without fully exhausting it, removes every record
+/// it was created to drain. Use [`Drain::keep_rest`] to stop early and retain
+/// whatever records haven't been yielded yet instead.
+///
+/// If a `Drain` is leaked, e.g. via [`mem::forget`](std::mem::forget), the records
+/// it was draining are conservatively left untouched in the Segment rather than
+/// removed, since we only ever shift bytes once we know how the `Drain` ended.
+pub struct Drain<'a, S: Storage>
+where
+    S::Record: Record,
+{
+    segment: &'a mut Segment<S>,
+    len: usize,
+    index: usize,
+}
+
+impl<S: Storage> Iterator for Drain<'_, S>
+where
+    S::Record: Copy + Record,
+{
+    type Item = S::Record;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.len {
+            return None;
+        }
+
+        // `Storage::get` handles wraparound, unlike `Segment::records`, which
+        // panics once a `RingSegment` has wrapped.
+        let record = self.segment.storage.get(self.index);
+        self.index += 1;
+        Some(record)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<S: Storage> ExactSizeIterator for Drain<'_, S> where S::Record: Copy + Record {}
+
+impl<S: Storage> Drain<'_, S>
+where
+    S::Record: Copy + Record,
+{
+    /// Stop iterating and retain every record that hasn't been yielded yet.
+    ///
+    /// Only the records already returned by [`Iterator::next`] are removed from the
+    /// Segment; the rest are left in place, unlike the default [`Drop`] behavior
+    /// which removes every record this `Drain` was created for.
+    pub fn keep_rest(self) {
+        let index = self.index;
+
+        // We are replacing the `Drop` impl below with the partial trim below,
+        // so make sure it doesn't also run once `this` goes out of scope.
+        let mut this = ManuallyDrop::new(self);
+        this.segment.trim(index);
+    }
+}
+
+impl<S: Storage> Drop for Drain<'_, S>
+where
+    S::Record: Record,
+{
+    fn drop(&mut self) {
+        self.segment.trim(self.len);
+    }
+}
+
+/// Iterator returned by [`Segment::iter`] over every record a [`Segment`] has ever
+/// held that's still retrievable: spilled, compressed blocks oldest first, then the
+/// records currently live in the hot buffer.
+pub struct Iter<'a, S: Storage>
+where
+    S::Record: Record,
+{
+    segment: &'a mut Segment<S>,
+    block: usize,
+    buffered: std::vec::IntoIter<S::Record>,
+    hot: usize,
+}
+
+impl<S: Storage> Iterator for Iter<'_, S>
+where
+    S::Record: Copy + Record,
+{
+    type Item = S::Record;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(record) = self.buffered.next() {
+            return Some(record);
+        }
+
+        if let Some(cold) = &mut self.segment.cold {
+            while self.block < cold.block_count() {
+                let bytes = cold
+                    .read_block(self.block)
+                    .expect("Cannot read spill block for Segment");
+                self.block += 1;
+
+                let records = S::Record::from_bytes_slice(&bytes).to_vec();
+                if !records.is_empty() {
+                    self.buffered = records.into_iter();
+                    return self.buffered.next();
+                }
+            }
+        }
+
+        let (first, second) = self.segment.as_slices();
+        if self.hot < first.len() {
+            let record = first[self.hot];
+            self.hot += 1;
+            return Some(record);
+        }
+
+        let index = self.hot - first.len();
+        if index < second.len() {
+            self.hot += 1;
+            return Some(second[index]);
+        }
+
+        None
+    }
 }
 
 #[cfg(test)]
@@ -249,10 +852,16 @@ mod tests {
         VecSegment::with_capacity(CAPACITY, trimmer)
     }
 
+    fn inline_segment(trimmer: Trimmer) -> InlineSegment<usize, CAPACITY> {
+        InlineSegment::new(trimmer)
+    }
+
     #[rstest]
     #[case(vec_segment(Trimmer::None))]
     #[case(off_heap_segment(Trimmer::None))]
     #[case(on_heap_segment(Trimmer::None))]
+    #[case(inline_segment(Trimmer::None))]
+    #[case(magic_segment(Trimmer::None))]
     fn test_push<S: Storage<Record = usize>>(#[case] mut segment: Segment<S>) {
         // Test records.
         assert!(segment.is_empty());
@@ -285,6 +894,7 @@ mod tests {
     #[case(vec_segment(Trimmer::Trim(100)), 100)]
     #[case(off_heap_segment(Trimmer::Trim(100)), 100)]
     #[case(on_heap_segment(Trimmer::Trim(100)), 100)]
+    #[case(inline_segment(Trimmer::Trim(100)), 100)]
     fn test_push_trimmer<S: Storage<Record = usize>>(
         #[case] mut segment: Segment<S>,
         #[case] trim: usize,
@@ -311,6 +921,7 @@ mod tests {
     #[case(vec_segment(Trimmer::None))]
     #[case(off_heap_segment(Trimmer::None))]
     #[case(on_heap_segment(Trimmer::None))]
+    #[case(inline_segment(Trimmer::None))]
     fn test_extend_from_slice<S: Storage<Record = usize>>(#[case] mut segment: Segment<S>) {
         // Test records.
         assert!(segment.is_empty());
@@ -343,12 +954,15 @@ mod tests {
     #[case(vec_segment(Trimmer::Trim(63)), 63)]
     #[case(off_heap_segment(Trimmer::Trim(63)), 63)]
     #[case(on_heap_segment(Trimmer::Trim(63)), 63)]
+    #[case(inline_segment(Trimmer::Trim(63)), 63)]
     #[case(vec_segment(Trimmer::Trim(CAPACITY)), CAPACITY)]
     #[case(off_heap_segment(Trimmer::Trim(CAPACITY)), CAPACITY)]
     #[case(on_heap_segment(Trimmer::Trim(CAPACITY)), CAPACITY)]
+    #[case(inline_segment(Trimmer::Trim(CAPACITY)), CAPACITY)]
     #[case(vec_segment(Trimmer::Trim(CAPACITY * 2)), CAPACITY * 2)]
     #[case(off_heap_segment(Trimmer::Trim(CAPACITY * 2)), CAPACITY * 2)]
     #[case(on_heap_segment(Trimmer::Trim(CAPACITY * 2)), CAPACITY * 2)]
+    #[case(inline_segment(Trimmer::Trim(CAPACITY * 2)), CAPACITY * 2)]
     fn test_extend_from_slice_trimmer<S: Storage<Record = usize>>(
         #[case] mut segment: Segment<S>,
         #[case] trim: usize,
@@ -381,4 +995,475 @@ mod tests {
             &more_records[..trimmed]
         );
     }
+
+    #[rstest]
+    #[case(vec_segment(Trimmer::None))]
+    #[case(off_heap_segment(Trimmer::None))]
+    #[case(on_heap_segment(Trimmer::None))]
+    #[case(inline_segment(Trimmer::None))]
+    fn test_drain<S: Storage<Record = usize>>(#[case] mut segment: Segment<S>) {
+        let records: [_; CAPACITY] = std::array::from_fn(|i| i * 2);
+        assert!(segment.extend_from_slice(&records).is_empty());
+
+        // Draining should yield the front records by value, in order.
+        let drained: Vec<_> = segment.drain(100).collect();
+        assert_eq!(drained, records[..100]);
+
+        // And the left-shift should have happened once the Drain was dropped.
+        assert_eq!(segment.len(), CAPACITY - 100);
+        assert_eq!(segment.records(), &records[100..]);
+    }
+
+    #[rstest]
+    #[case(vec_segment(Trimmer::None))]
+    #[case(off_heap_segment(Trimmer::None))]
+    #[case(on_heap_segment(Trimmer::None))]
+    #[case(inline_segment(Trimmer::None))]
+    fn test_drain_abandoned<S: Storage<Record = usize>>(#[case] mut segment: Segment<S>) {
+        let records: [_; CAPACITY] = std::array::from_fn(|i| i * 2);
+        assert!(segment.extend_from_slice(&records).is_empty());
+
+        // Dropping a Drain without iterating it still removes every record it covers.
+        drop(segment.drain(100));
+        assert_eq!(segment.len(), CAPACITY - 100);
+        assert_eq!(segment.records(), &records[100..]);
+    }
+
+    #[rstest]
+    #[case(vec_segment(Trimmer::None))]
+    #[case(off_heap_segment(Trimmer::None))]
+    #[case(on_heap_segment(Trimmer::None))]
+    #[case(inline_segment(Trimmer::None))]
+    fn test_drain_keep_rest<S: Storage<Record = usize>>(#[case] mut segment: Segment<S>) {
+        let records: [_; CAPACITY] = std::array::from_fn(|i| i * 2);
+        assert!(segment.extend_from_slice(&records).is_empty());
+
+        let mut drain = segment.drain(100);
+        let yielded: Vec<_> = (&mut drain).take(40).collect();
+        assert_eq!(yielded, records[..40]);
+        drain.keep_rest();
+
+        // Only the 40 yielded records should have been removed, the other 60 remain.
+        assert_eq!(segment.len(), CAPACITY - 40);
+        assert_eq!(segment.records(), &records[40..]);
+    }
+
+    #[rstest]
+    #[case(vec_segment(Trimmer::None))]
+    #[case(off_heap_segment(Trimmer::None))]
+    #[case(on_heap_segment(Trimmer::None))]
+    #[case(inline_segment(Trimmer::None))]
+    fn test_drain_more_than_len<S: Storage<Record = usize>>(#[case] mut segment: Segment<S>) {
+        let records: [_; CAPACITY] = std::array::from_fn(|i| i * 2);
+        assert!(segment.extend_from_slice(&records).is_empty());
+
+        // Draining more than the Segment holds should just drain everything.
+        let drained: Vec<_> = segment.drain(CAPACITY * 2).collect();
+        assert_eq!(drained, records);
+        assert!(segment.is_empty());
+    }
+
+    #[test]
+    fn test_drain_on_wrapped_ring_segment() {
+        let mut segment = ring_segment(Trimmer::None);
+        let records: [_; CAPACITY] = std::array::from_fn(|i| i * 2);
+        assert!(segment.extend_from_slice(&records).is_empty());
+
+        // Trim half, then push more than that half back in so the records
+        // wrap around to the start of the buffer, same as `test_ring_wraps_around_buffer`.
+        segment.trim(CAPACITY / 2);
+        let more: [_; CAPACITY / 2] = std::array::from_fn(|i| i * 3);
+        assert!(segment.extend_from_slice(&more).is_empty());
+
+        // Draining should yield records in logical order without panicking,
+        // even though `records()` can't be called on this wrapped buffer.
+        let mut expected = records[CAPACITY / 2..].to_vec();
+        expected.extend_from_slice(&more);
+
+        let drained: Vec<_> = segment.drain(10).collect();
+        assert_eq!(drained, expected[..10]);
+        assert_eq!(segment.len(), CAPACITY - 10);
+
+        let (first, second) = segment.as_slices();
+        let remaining: Vec<_> = first.iter().chain(second).copied().collect();
+        assert_eq!(remaining, expected[10..]);
+    }
+
+    fn ring_segment(trimmer: Trimmer) -> RingSegment<usize> {
+        RingSegment::with_capacity(CAPACITY, trimmer)
+    }
+
+    fn magic_segment(trimmer: Trimmer) -> MagicSegment<usize> {
+        MagicSegment::with_capacity(CAPACITY, trimmer)
+    }
+
+    #[test]
+    fn test_ring_trim_push_does_not_wrap() {
+        let mut segment = ring_segment(Trimmer::None);
+        let records: [_; CAPACITY] = std::array::from_fn(|i| i * 2);
+        assert!(segment.extend_from_slice(&records).is_empty());
+        assert_eq!(segment.records(), &records);
+
+        // Trimming the front makes room without moving any of the survivors.
+        segment.trim(CAPACITY / 2);
+        assert_eq!(segment.records(), &records[CAPACITY / 2..]);
+    }
+
+    #[test]
+    fn test_ring_wraps_around_buffer() {
+        let mut segment = ring_segment(Trimmer::None);
+        let records: [_; CAPACITY] = std::array::from_fn(|i| i * 2);
+        assert!(segment.extend_from_slice(&records).is_empty());
+
+        // Trim half, then push more than that half back in; the new records
+        // wrap around to the start of the buffer.
+        segment.trim(CAPACITY / 2);
+        let more: [_; CAPACITY / 2] = std::array::from_fn(|i| i * 3);
+        assert!(segment.extend_from_slice(&more).is_empty());
+
+        // Records are now physically non-contiguous, so `records()` panics...
+        assert!(std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| segment.records()))
+            .is_err());
+
+        // ...but `as_slices` reports the two physical runs correctly.
+        let (first, second) = segment.as_slices();
+        assert_eq!(first, &records[CAPACITY / 2..]);
+        assert_eq!(second, &more);
+    }
+
+    #[test]
+    fn test_ring_as_slices_unwrapped() {
+        let mut segment = ring_segment(Trimmer::None);
+        assert_eq!(segment.as_slices(), (&[][..], &[][..]));
+
+        let records = [1, 2, 3];
+        assert!(segment.extend_from_slice(&records).is_empty());
+        assert_eq!(segment.as_slices(), (&records[..], &[][..]));
+    }
+
+    #[test]
+    fn test_magic_wraps_without_splitting() {
+        let mut segment = magic_segment(Trimmer::None);
+        let records: Vec<_> = (0..segment.capacity()).map(|i| i * 2).collect();
+        assert!(segment.extend_from_slice(&records).is_empty());
+
+        // Trim half, then push more than that half back in; unlike RingSegment,
+        // the mirrored mapping means the live records are still one contiguous
+        // run even though they physically straddle the seam.
+        let half = segment.capacity() / 2;
+        segment.trim(half);
+        let more: Vec<_> = (0..half).map(|i| i * 3).collect();
+        assert!(segment.extend_from_slice(&more).is_empty());
+
+        let expected: Vec<_> = records[half..].iter().chain(more.iter()).copied().collect();
+        assert_eq!(segment.records(), expected.as_slice());
+        assert_eq!(segment.as_slices(), (expected.as_slice(), &[][..]));
+    }
+
+    /// A record whose size doesn't evenly divide the host page size, so rounding the
+    /// mirrored region up to whole pages alone would leave a gap before the seam.
+    #[repr(C)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, bytemuck::Pod, bytemuck::Zeroable)]
+    struct Wonky {
+        a: u64,
+        b: u64,
+        c: u64,
+    }
+
+    #[test]
+    fn test_magic_wraps_without_splitting_non_page_divisor_record() {
+        let mut segment: MagicSegment<Wonky> = MagicSegment::with_capacity(CAPACITY, Trimmer::None);
+        let records: Vec<_> = (0..segment.capacity())
+            .map(|i| Wonky { a: i as u64, b: i as u64 * 2, c: i as u64 * 3 })
+            .collect();
+        assert!(segment.extend_from_slice(&records).is_empty());
+
+        // Trim half, then push more than that half back in, same as
+        // `test_magic_wraps_without_splitting`, except `Wonky` is 24 bytes and does
+        // not evenly divide a 4 KiB page, so this exercises the gap-free rounding of
+        // the mirrored region instead of the happy case where it divides evenly.
+        let half = segment.capacity() / 2;
+        segment.trim(half);
+        let more: Vec<_> = (0..half)
+            .map(|i| Wonky { a: i as u64 * 5, b: i as u64 * 6, c: i as u64 * 7 })
+            .collect();
+        assert!(segment.extend_from_slice(&more).is_empty());
+
+        let expected: Vec<_> = records[half..].iter().chain(more.iter()).copied().collect();
+        assert_eq!(segment.records(), expected.as_slice());
+        assert_eq!(segment.as_slices(), (expected.as_slice(), &[][..]));
+    }
+
+    #[rstest]
+    #[case(vec_segment(Trimmer::None))]
+    #[case(off_heap_segment(Trimmer::None))]
+    #[case(on_heap_segment(Trimmer::None))]
+    #[case(inline_segment(Trimmer::None))]
+    fn test_integrity_disabled_by_default<S: Storage<Record = usize>>(
+        #[case] mut segment: Segment<S>,
+    ) {
+        assert_eq!(segment.checksum(), None);
+        assert!(segment.push(1).is_none());
+
+        // With tracking disabled there's nothing to compare against, so this
+        // always reports success.
+        assert!(segment.verify());
+    }
+
+    #[rstest]
+    #[case(vec_segment(Trimmer::None))]
+    #[case(off_heap_segment(Trimmer::None))]
+    #[case(on_heap_segment(Trimmer::None))]
+    #[case(inline_segment(Trimmer::None))]
+    fn test_integrity_tracks_incremental_appends<S: Storage<Record = usize>>(
+        #[case] mut segment: Segment<S>,
+    ) {
+        segment.enable_integrity();
+        assert!(segment.extend_from_slice(&[1, 2, 3]).is_empty());
+        assert!(segment.push(4).is_none());
+        assert!(segment.verify());
+
+        // An equivalent Segment, built in one shot, should agree on the checksum.
+        let mut expected = vec_segment(Trimmer::None);
+        expected.enable_integrity();
+        assert!(expected.extend_from_slice(&[1, 2, 3, 4]).is_empty());
+        assert_eq!(segment.checksum(), expected.checksum());
+    }
+
+    #[rstest]
+    #[case(vec_segment(Trimmer::None))]
+    #[case(off_heap_segment(Trimmer::None))]
+    #[case(on_heap_segment(Trimmer::None))]
+    #[case(inline_segment(Trimmer::None))]
+    fn test_integrity_resyncs_after_trim<S: Storage<Record = usize>>(
+        #[case] mut segment: Segment<S>,
+    ) {
+        segment.enable_integrity();
+        let records: [_; CAPACITY] = std::array::from_fn(|i| i * 2);
+        assert!(segment.extend_from_slice(&records).is_empty());
+
+        segment.trim(CAPACITY / 2);
+        assert!(segment.verify());
+
+        let mut expected = vec_segment(Trimmer::None);
+        expected.enable_integrity();
+        assert!(
+            expected
+                .extend_from_slice(&records[CAPACITY / 2..])
+                .is_empty()
+        );
+        assert_eq!(segment.checksum(), expected.checksum());
+    }
+
+    #[rstest]
+    #[case(vec_segment(Trimmer::None))]
+    #[case(off_heap_segment(Trimmer::None))]
+    #[case(on_heap_segment(Trimmer::None))]
+    #[case(inline_segment(Trimmer::None))]
+    fn test_integrity_resets_on_clear<S: Storage<Record = usize>>(#[case] mut segment: Segment<S>) {
+        segment.enable_integrity();
+        assert!(segment.extend_from_slice(&[1, 2, 3]).is_empty());
+
+        segment.clear();
+        assert!(segment.verify());
+
+        let mut expected = vec_segment(Trimmer::None);
+        expected.enable_integrity();
+        assert_eq!(segment.checksum(), expected.checksum());
+    }
+
+    #[test]
+    fn test_integrity_survives_ring_wrap() {
+        let mut segment = ring_segment(Trimmer::None);
+        segment.enable_integrity();
+
+        let records: [_; CAPACITY] = std::array::from_fn(|i| i * 2);
+        assert!(segment.extend_from_slice(&records).is_empty());
+        segment.trim(CAPACITY / 2);
+
+        let more: [_; CAPACITY / 2] = std::array::from_fn(|i| i * 3);
+        assert!(segment.extend_from_slice(&more).is_empty());
+        assert!(segment.verify());
+
+        // The checksum is computed over the two physical runs in order, so it should
+        // agree with a fresh Segment holding the same logical records contiguously.
+        let mut expected = vec_segment(Trimmer::None);
+        expected.enable_integrity();
+        assert!(
+            expected
+                .extend_from_slice(&records[CAPACITY / 2..])
+                .is_empty()
+        );
+        assert!(expected.extend_from_slice(&more).is_empty());
+        assert_eq!(segment.checksum(), expected.checksum());
+    }
+
+    #[rstest]
+    #[case(vec_segment(Trimmer::None))]
+    #[case(off_heap_segment(Trimmer::None))]
+    #[case(on_heap_segment(Trimmer::None))]
+    #[case(inline_segment(Trimmer::None))]
+    fn test_dedup_no_duplicates_is_write_free<S: Storage<Record = usize>>(
+        #[case] mut segment: Segment<S>,
+    ) {
+        let records = [1, 2, 3, 4, 5];
+        assert!(segment.extend_from_slice(&records).is_empty());
+
+        segment.dedup();
+        assert_eq!(segment.records(), &records);
+    }
+
+    #[rstest]
+    #[case(vec_segment(Trimmer::None))]
+    #[case(off_heap_segment(Trimmer::None))]
+    #[case(on_heap_segment(Trimmer::None))]
+    #[case(inline_segment(Trimmer::None))]
+    fn test_dedup_collapses_consecutive_runs<S: Storage<Record = usize>>(
+        #[case] mut segment: Segment<S>,
+    ) {
+        let records = [1, 1, 2, 3, 3, 3, 4, 5, 5];
+        assert!(segment.extend_from_slice(&records).is_empty());
+
+        segment.dedup();
+        assert_eq!(segment.records(), &[1, 2, 3, 4, 5]);
+        assert_eq!(segment.len(), 5);
+    }
+
+    #[rstest]
+    #[case(vec_segment(Trimmer::None))]
+    #[case(off_heap_segment(Trimmer::None))]
+    #[case(on_heap_segment(Trimmer::None))]
+    #[case(inline_segment(Trimmer::None))]
+    fn test_dedup_only_collapses_consecutive_not_all_equal<S: Storage<Record = usize>>(
+        #[case] mut segment: Segment<S>,
+    ) {
+        let records = [1, 2, 1, 1, 2];
+        assert!(segment.extend_from_slice(&records).is_empty());
+
+        segment.dedup();
+        assert_eq!(segment.records(), &[1, 2, 1, 2]);
+    }
+
+    #[rstest]
+    #[case(vec_segment(Trimmer::None))]
+    #[case(off_heap_segment(Trimmer::None))]
+    #[case(on_heap_segment(Trimmer::None))]
+    #[case(inline_segment(Trimmer::None))]
+    fn test_dedup_by_uses_custom_equality<S: Storage<Record = usize>>(
+        #[case] mut segment: Segment<S>,
+    ) {
+        let records = [10, 11, 20, 21, 30];
+        assert!(segment.extend_from_slice(&records).is_empty());
+
+        // Treat records as equal if they fall in the same group of ten.
+        segment.dedup_by(|a, b| a / 10 == b / 10);
+        assert_eq!(segment.records(), &[10, 20, 30]);
+    }
+
+    #[rstest]
+    #[case(vec_segment(Trimmer::None))]
+    #[case(off_heap_segment(Trimmer::None))]
+    #[case(on_heap_segment(Trimmer::None))]
+    #[case(inline_segment(Trimmer::None))]
+    fn test_dedup_resyncs_integrity<S: Storage<Record = usize>>(#[case] mut segment: Segment<S>) {
+        segment.enable_integrity();
+        assert!(segment.extend_from_slice(&[1, 1, 2, 2, 3]).is_empty());
+
+        segment.dedup();
+        assert!(segment.verify());
+
+        let mut expected = vec_segment(Trimmer::None);
+        expected.enable_integrity();
+        assert!(expected.extend_from_slice(&[1, 2, 3]).is_empty());
+        assert_eq!(segment.checksum(), expected.checksum());
+    }
+
+    #[test]
+    fn test_dedup_across_ring_wraparound() {
+        let mut segment = ring_segment(Trimmer::None);
+        let records: [_; CAPACITY] = std::array::from_fn(|_| 7);
+        assert!(segment.extend_from_slice(&records).is_empty());
+
+        // Trim half, then wrap a duplicate-laden batch around the buffer.
+        segment.trim(CAPACITY / 2);
+        let more: [_; CAPACITY / 2] = std::array::from_fn(|_| 7);
+        assert!(segment.extend_from_slice(&more).is_empty());
+
+        // Every record is still `7`, physically wrapped around the buffer; dedup
+        // should collapse all of them down to one, respecting wraparound.
+        segment.dedup();
+        assert_eq!(segment.len(), 1);
+        assert_eq!(segment.as_slices(), (&[7][..], &[][..]));
+    }
+
+    /// Unique path for a spill file, cleaned up by the caller once the test is done with it.
+    fn spill_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("crosstream-ring-test-{name}-{}", std::process::id()))
+    }
+
+    #[test]
+    fn test_compress_trim_spills_evicted_records() {
+        let spill = spill_path("compress-trim");
+        let trimmer = Trimmer::Compress {
+            spill: spill.clone(),
+            window: 64,
+        };
+        let mut segment: VecSegment<usize> = VecSegment::with_capacity(CAPACITY, trimmer);
+
+        let records: [_; CAPACITY] = std::array::from_fn(|i| i * 2);
+        assert!(segment.extend_from_slice(&records).is_empty());
+
+        // Pushing past capacity spills exactly enough of the oldest records to fit,
+        // rather than rejecting the new one.
+        assert_eq!(segment.push(100), None);
+        assert_eq!(segment.len(), CAPACITY);
+        assert_eq!(&segment.records()[..CAPACITY - 1], &records[1..]);
+        assert_eq!(segment.records()[CAPACITY - 1], 100);
+
+        // The evicted record is still readable through `iter`, ahead of the hot records.
+        let all: Vec<_> = segment.iter().collect();
+        assert_eq!(all[0], records[0]);
+        assert_eq!(&all[1..], segment.records());
+
+        let _ = std::fs::remove_file(&spill);
+    }
+
+    #[test]
+    fn test_compress_trim_preserves_order_across_many_evictions() {
+        let spill = spill_path("compress-multi");
+        let trimmer = Trimmer::Compress {
+            spill: spill.clone(),
+            window: 64,
+        };
+        let mut segment: VecSegment<usize> = VecSegment::with_capacity(8, trimmer);
+
+        for i in 0..32 {
+            assert_eq!(segment.push(i), None);
+        }
+
+        let expected: Vec<_> = (0..32).collect();
+        assert_eq!(segment.iter().collect::<Vec<_>>(), expected);
+
+        let _ = std::fs::remove_file(&spill);
+    }
+
+    #[test]
+    fn test_compress_trim_history_survives_evicting_everything() {
+        let spill = spill_path("compress-evict-all");
+        let trimmer = Trimmer::Compress {
+            spill: spill.clone(),
+            window: 64,
+        };
+        let mut segment: VecSegment<usize> = VecSegment::with_capacity(4, trimmer);
+
+        assert!(segment.extend_from_slice(&[1, 2, 3, 4]).is_empty());
+
+        // This append needs to evict every currently hot record to fit, which takes the
+        // internal `trim` through its full-trim/`clear` fast path; the records evicted to
+        // get there should still come back out of `iter` rather than being forgotten.
+        assert!(segment.extend_from_slice(&[5, 6, 7, 8]).is_empty());
+        assert_eq!(segment.iter().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5, 6, 7, 8]);
+
+        let _ = std::fs::remove_file(&spill);
+    }
 }
@@ -1,19 +1,35 @@
 //! Definition of storage engine that backs a segment.
+//!
+//! This module only depends on `core`/`alloc`, so it's available even when this crate is
+//! built with `default-features = false` under `#![no_std]`. The one exception is the
+//! `mmap` feature (on by default, requires `std`): [`OffHeapStorage`] and [`FileBackedStorage`]
+//! pull in `memmap2`, which needs an OS to map pages, so they and their backing
+//! [`OffHeap`]/[`FileBacked`] memory types are gated behind it.
 
-use crate::Record;
-use core::slice;
+use crate::{Record, TryReserveError};
+use alloc::{boxed::Box, vec::Vec};
+use allocator_api2::alloc::{Allocator, Global};
+use core::{alloc::Layout, cmp::min, marker::PhantomData, mem::MaybeUninit, ptr::NonNull, slice};
+#[cfg(feature = "mmap")]
 use memmap2::{MmapMut, MmapOptions};
-use std::{
-    alloc::{self, Layout, handle_alloc_error},
-    marker::PhantomData,
-};
+#[cfg(feature = "mmap")]
+use std::{fs::OpenOptions, io, path::Path};
 
 /// Type alias for [`MemStorage`] backed by [`OnHeap`] memory.
-pub type OnHeapStorage<T> = MemStorage<T, OnHeap>;
+///
+/// Defaults to the [`Global`] allocator; pass a different `A: Allocator` to
+/// back a `Segment` with a bump arena or a per-thread pool instead, which
+/// matters when many segments are cycled and per-allocation syscalls dominate.
+pub type OnHeapStorage<T, A = Global> = MemStorage<T, OnHeap<A>>;
 
 /// Type alias for [`MemStorage`] backed by [`OffHeap`] memory.
+#[cfg(feature = "mmap")]
 pub type OffHeapStorage<T> = MemStorage<T, OffHeap>;
 
+/// Type alias for [`MemStorage`] backed by a [`FileBacked`] mapping.
+#[cfg(feature = "mmap")]
+pub type FileBackedStorage<T> = MemStorage<T, FileBacked>;
+
 /// Storage engine that holds a contiguous sequence of records.
 ///
 /// # Internal
@@ -62,7 +78,63 @@ pub trait Storage {
     fn clear(&mut self);
 
     /// Return reference to all records in storage.
+    ///
+    /// # Panics
+    ///
+    /// Engines that store records non-contiguously (i.e. [`RingStorage`] once
+    /// it has wrapped) panic here; use [`Storage::records_slices`] instead.
     fn records(&self) -> &[Self::Record];
+
+    /// Return the two contiguous runs that together make up all records in storage.
+    ///
+    /// Most engines keep records fully contiguous, so the second slice is always
+    /// empty; the default implementation just defers to [`Storage::records`]. Engines
+    /// that wrap records around a fixed-capacity buffer instead of shifting them on
+    /// every trim (see [`RingStorage`]) override this to avoid ever copying bytes.
+    fn records_slices(&self) -> (&[Self::Record], &[Self::Record]) {
+        (self.records(), &[])
+    }
+
+    /// Read the record at logical index `index`, where `0` is the first record.
+    ///
+    /// # Invariants
+    ///
+    /// * `index < self.length()`
+    fn get(&self, index: usize) -> Self::Record
+    where
+        Self::Record: Copy;
+
+    /// Overwrite the record at logical index `index`, where `0` is the first record.
+    ///
+    /// # Invariants
+    ///
+    /// * `index < self.length()`
+    fn set(&mut self, index: usize, record: Self::Record);
+
+    /// Shrink the number of records held to `len`, discarding everything after it.
+    ///
+    /// Unlike [`Storage::trim`], which discards from the front, this discards from
+    /// the back, keeping whichever records are already in the first `len` slots.
+    ///
+    /// # Invariants
+    ///
+    /// * `len <= self.length()`
+    fn truncate(&mut self, len: usize);
+
+    /// Attempt to grow usable capacity by `additional` records, without
+    /// relocating existing records.
+    ///
+    /// Returns `true` if capacity grew, `false` (the default) if this engine
+    /// has no spare reserved capacity to grow into -- e.g. [`VecStorage`],
+    /// which would need to reallocate and copy instead, so it declines rather
+    /// than silently paying for that here.
+    ///
+    /// # Arguments
+    ///
+    /// * `additional` - Number of additional records of capacity to request.
+    fn try_grow(&mut self, _additional: usize) -> bool {
+        false
+    }
 }
 
 /// Storage engine that uses [`Vec`] for memory.
@@ -83,7 +155,19 @@ impl<T: Record + Copy> VecStorage<T> {
     ///
     /// * `capacity` - Maximum capacity of this storage engine.
     pub(crate) fn new(capacity: usize) -> Self {
-        Self(Vec::with_capacity(capacity))
+        Self::try_new(capacity).expect("Cannot allocate capacity for VecStorage")
+    }
+
+    /// Fallible variant of [`VecStorage::new`] that returns a [`TryReserveError`]
+    /// instead of aborting when the requested capacity could not be allocated.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum capacity of this storage engine.
+    pub(crate) fn try_new(capacity: usize) -> Result<Self, TryReserveError> {
+        let mut records = Vec::new();
+        records.try_reserve_exact(capacity)?;
+        Ok(Self(records))
     }
 }
 
@@ -117,6 +201,248 @@ impl<T: Copy> Storage for VecStorage<T> {
     fn records(&self) -> &[T] {
         &self.0
     }
+
+    fn get(&self, index: usize) -> T
+    where
+        T: Copy,
+    {
+        self.0[index]
+    }
+
+    fn set(&mut self, index: usize, record: T) {
+        self.0[index] = record;
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.0.truncate(len);
+    }
+}
+
+/// Storage engine that holds records inline, on the stack, with no heap allocation.
+///
+/// Good fit for workloads that keep many small, short-lived segments, where the
+/// capacity is known at compile time; allocating heap/off-heap memory for each one
+/// would waste a syscall and a pointer chase. Since a [`Segment`](crate::Segment)
+/// never grows past its initial capacity, there's no spill-to-heap transition to
+/// worry about either.
+#[derive(Debug)]
+pub struct InlineStorage<T, const N: usize> {
+    records: [MaybeUninit<T>; N],
+    length: usize,
+}
+
+impl<T: Record + Copy, const N: usize> InlineStorage<T, N> {
+    /// Create a new instance of [`Storage`] engine with records held inline.
+    pub(crate) fn new() -> Self {
+        Self {
+            records: [MaybeUninit::uninit(); N],
+            length: 0,
+        }
+    }
+}
+
+impl<T: Record + Copy, const N: usize> Storage for InlineStorage<T, N> {
+    type Record = T;
+
+    fn capacity(&self) -> usize {
+        N
+    }
+
+    fn length(&self) -> usize {
+        self.length
+    }
+
+    fn remaining(&self) -> usize {
+        N - self.length
+    }
+
+    fn trim(&mut self, len: usize) {
+        self.records.copy_within(len..self.length, 0);
+        self.length -= len;
+    }
+
+    fn extend(&mut self, records: &[T]) {
+        for (slot, record) in self.records[self.length..].iter_mut().zip(records) {
+            slot.write(*record);
+        }
+
+        self.length += records.len();
+    }
+
+    fn clear(&mut self) {
+        self.length = 0;
+    }
+
+    fn records(&self) -> &[T] {
+        // Safety: The first `length` slots are always initialized by `extend`.
+        unsafe { slice::from_raw_parts(self.records.as_ptr().cast::<T>(), self.length) }
+    }
+
+    fn get(&self, index: usize) -> T
+    where
+        T: Copy,
+    {
+        // Safety: index < self.length, which are always initialized.
+        unsafe { self.records[index].assume_init() }
+    }
+
+    fn set(&mut self, index: usize, record: T) {
+        self.records[index].write(record);
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.length = len;
+    }
+}
+
+/// Storage engine backed by a fixed-capacity circular buffer.
+///
+/// Unlike [`VecStorage`]/[`MemStorage`], which left-shift the survivors on every
+/// [`Storage::trim`] (an O(M) memmove), this advances a `head` index modulo
+/// capacity instead, making both `trim` and `extend` genuine O(1) operations with
+/// no byte movement — analogous to how [`VecDeque`](std::collections::VecDeque)
+/// relocates only the shorter contiguous run rather than shifting everything.
+///
+/// Records may physically wrap around the end of the buffer, so [`Storage::records`]
+/// only works while unwrapped; use [`Storage::records_slices`] (or [`Segment::as_slices`](crate::Segment::as_slices))
+/// to read records regardless of wrap state.
+#[derive(Debug)]
+pub struct RingStorage<T> {
+    buffer: Box<[MaybeUninit<T>]>,
+    head: usize,
+    length: usize,
+}
+
+impl<T: Record + Copy> RingStorage<T> {
+    /// Create a new instance of [`Storage`] engine backed by a circular buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if requested capacity could not be allocated.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum capacity of this storage engine.
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self::try_new(capacity).expect("Cannot allocate capacity for RingStorage")
+    }
+
+    /// Fallible variant of [`RingStorage::new`] that returns a [`TryReserveError`]
+    /// instead of aborting when the requested capacity could not be allocated.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum capacity of this storage engine.
+    pub(crate) fn try_new(capacity: usize) -> Result<Self, TryReserveError> {
+        let mut buffer = Vec::new();
+        buffer.try_reserve_exact(capacity)?;
+        buffer.resize_with(capacity, MaybeUninit::uninit);
+
+        Ok(Self {
+            buffer: buffer.into_boxed_slice(),
+            head: 0,
+            length: 0,
+        })
+    }
+
+    /// Index of the first unoccupied slot, i.e. where the next `extend` starts writing.
+    fn tail(&self) -> usize {
+        (self.head + self.length) % self.capacity().max(1)
+    }
+
+    /// Cast a slice of initialized [`MaybeUninit`] slots to the records they hold.
+    fn as_slice(slots: &[MaybeUninit<T>]) -> &[T] {
+        // Safety: Callers only pass slots within the occupied `head..head + length` run.
+        unsafe { slice::from_raw_parts(slots.as_ptr().cast::<T>(), slots.len()) }
+    }
+}
+
+impl<T: Record + Copy> Storage for RingStorage<T> {
+    type Record = T;
+
+    fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    fn length(&self) -> usize {
+        self.length
+    }
+
+    fn remaining(&self) -> usize {
+        self.capacity() - self.length()
+    }
+
+    fn trim(&mut self, len: usize) {
+        self.head = (self.head + len) % self.capacity().max(1);
+        self.length -= len;
+    }
+
+    fn extend(&mut self, records: &[T]) {
+        let capacity = self.capacity();
+        let tail = self.tail();
+
+        let first_len = min(records.len(), capacity - tail);
+        let (first, second) = records.split_at(first_len);
+
+        for (slot, record) in self.buffer[tail..tail + first_len].iter_mut().zip(first) {
+            slot.write(*record);
+        }
+
+        for (slot, record) in self.buffer[..second.len()].iter_mut().zip(second) {
+            slot.write(*record);
+        }
+
+        self.length += records.len();
+    }
+
+    fn clear(&mut self) {
+        self.head = 0;
+        self.length = 0;
+    }
+
+    fn records(&self) -> &[T] {
+        let (first, second) = self.records_slices();
+        assert!(
+            second.is_empty(),
+            "RingStorage::records() cannot be used once records have wrapped; use records_slices() instead"
+        );
+        first
+    }
+
+    fn records_slices(&self) -> (&[T], &[T]) {
+        if self.length == 0 {
+            return (&[], &[]);
+        }
+
+        let capacity = self.capacity();
+        if self.head + self.length <= capacity {
+            (Self::as_slice(&self.buffer[self.head..self.head + self.length]), &[])
+        } else {
+            let wrapped = self.head + self.length - capacity;
+            (
+                Self::as_slice(&self.buffer[self.head..capacity]),
+                Self::as_slice(&self.buffer[..wrapped]),
+            )
+        }
+    }
+
+    fn get(&self, index: usize) -> T
+    where
+        T: Copy,
+    {
+        let physical = (self.head + index) % self.capacity().max(1);
+        // Safety: physical falls within the occupied head..head + length run (mod capacity).
+        unsafe { self.buffer[physical].assume_init() }
+    }
+
+    fn set(&mut self, index: usize, record: T) {
+        let physical = (self.head + index) % self.capacity().max(1);
+        self.buffer[physical].write(record);
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.length = len;
+    }
 }
 
 /// Storage engine that uses raw (byte addressable) memory to hold records.
@@ -135,9 +461,45 @@ pub struct MemStorage<T, M> {
     mem: M,
     length: usize,
     capacity: usize,
+    reserved_capacity: usize,
     phantom: PhantomData<T>,
 }
 
+impl<T, M> MemStorage<T, M> {
+    /// Grow usable capacity by `extra_records`, up to the ceiling reserved
+    /// when this storage engine was created.
+    ///
+    /// Since the backing memory already reserves address space out to
+    /// `reserved_capacity`, this is just an accounting change -- no
+    /// relocation or copy, unlike growing a `Vec`.
+    ///
+    /// Returns `false` (and leaves capacity unchanged) if `extra_records`
+    /// would exceed the reserved ceiling.
+    ///
+    /// # Arguments
+    ///
+    /// * `extra_records` - Number of additional records of capacity to request.
+    fn grow(&mut self, extra_records: usize) -> bool {
+        if self.capacity + extra_records > self.reserved_capacity {
+            return false;
+        }
+
+        self.capacity += extra_records;
+        true
+    }
+}
+
+/// Hook letting a [`MemStorage`] backing memory type react to changes in the
+/// number of records held.
+///
+/// No-op for purely in-memory backends ([`OnHeap`], [`OffHeap`]); [`FileBacked`]
+/// overrides it to mirror the new length into its on-disk header, so a
+/// reopened file resumes with the right length instead of zero.
+pub(crate) trait TrackLength {
+    fn set_length(&mut self, _length: usize) {}
+}
+
+#[cfg(feature = "mmap")]
 impl<T: Record> OffHeapStorage<T> {
     /// Create a new instance of [`Storage`] engine backed by off-heap memory.
     ///
@@ -149,17 +511,164 @@ impl<T: Record> OffHeapStorage<T> {
     ///
     /// * `capacity` - Maximum capacity of this storage engine.
     pub(crate) fn new(capacity: usize) -> Self {
-        Self {
-            mem: OffHeap::alloc(capacity * T::size()),
+        Self::try_new(capacity).expect("Cannot allocate capacity for OffHeapStorage")
+    }
+
+    /// Fallible variant of [`OffHeapStorage::new`] that returns a [`TryReserveError`]
+    /// instead of aborting when the requested capacity could not be allocated.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum capacity of this storage engine.
+    pub(crate) fn try_new(capacity: usize) -> Result<Self, TryReserveError> {
+        Self::try_new_with(capacity, HugePageSize::None)
+    }
+
+    /// Create a new instance of [`Storage`] engine backed by off-heap memory,
+    /// requesting the given [`HugePageSize`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if requested capacity could not be allocated.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum capacity of this storage engine.
+    /// * `huge_pages` - Page class to request for the backing mapping.
+    pub(crate) fn new_with(capacity: usize, huge_pages: HugePageSize) -> Self {
+        Self::try_new_with(capacity, huge_pages)
+            .expect("Cannot allocate capacity for OffHeapStorage")
+    }
+
+    /// Fallible variant of [`OffHeapStorage::new_with`] that returns a
+    /// [`TryReserveError`] instead of aborting when the requested capacity
+    /// could not be allocated.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum capacity of this storage engine.
+    /// * `huge_pages` - Page class to request for the backing mapping.
+    pub(crate) fn try_new_with(
+        capacity: usize,
+        huge_pages: HugePageSize,
+    ) -> Result<Self, TryReserveError> {
+        Ok(Self {
+            mem: OffHeap::try_alloc_with(capacity * T::size(), huge_pages)?,
             length: 0,
             capacity,
+            reserved_capacity: capacity,
             phantom: PhantomData,
-        }
+        })
+    }
+
+    /// Page class actually granted to this storage engine's backing mapping;
+    /// may fall short of what was requested (see [`HugePageSize::Explicit`]).
+    pub(crate) fn huge_page_size(&self) -> HugePageSize {
+        self.mem.huge_page_size()
+    }
+
+    /// Create a new instance of [`Storage`] engine backed by off-heap memory,
+    /// reserving address space for `reserved_capacity` records up front but
+    /// only eagerly committing the first `capacity`.
+    ///
+    /// Growing usable capacity later via [`Storage::try_grow`], up to
+    /// `reserved_capacity`, is then just an accounting change: the kernel
+    /// already laid out the full reservation and commits physical pages
+    /// lazily, on first touch, as more of it is actually written to.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the reservation could not be made.
+    /// * Panics if `reserved_capacity < capacity`.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Initial usable capacity of this storage engine.
+    /// * `reserved_capacity` - Ceiling this storage engine can grow to.
+    /// * `huge_pages` - Page class to request for the backing mapping.
+    pub(crate) fn reserve(
+        capacity: usize,
+        reserved_capacity: usize,
+        huge_pages: HugePageSize,
+    ) -> Self {
+        Self::try_reserve(capacity, reserved_capacity, huge_pages)
+            .expect("Cannot reserve address space for OffHeapStorage")
+    }
+
+    /// Fallible variant of [`OffHeapStorage::reserve`] that returns a
+    /// [`TryReserveError`] instead of aborting when the reservation could not
+    /// be made.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `reserved_capacity < capacity`.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Initial usable capacity of this storage engine.
+    /// * `reserved_capacity` - Ceiling this storage engine can grow to.
+    /// * `huge_pages` - Page class to request for the backing mapping.
+    pub(crate) fn try_reserve(
+        capacity: usize,
+        reserved_capacity: usize,
+        huge_pages: HugePageSize,
+    ) -> Result<Self, TryReserveError> {
+        assert!(
+            reserved_capacity >= capacity,
+            "reserved_capacity must be >= capacity"
+        );
+
+        Ok(Self {
+            mem: OffHeap::try_reserve_with(reserved_capacity * T::size(), huge_pages)?,
+            length: 0,
+            capacity,
+            reserved_capacity,
+            phantom: PhantomData,
+        })
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl<T: Record> FileBackedStorage<T> {
+    /// Open (creating if needed) a file-backed [`Storage`] engine at `path`,
+    /// so its records survive a process restart.
+    ///
+    /// If `path` already holds a header matching `capacity` and `T::size()`,
+    /// the `length` persisted there is restored. A header whose magic matches
+    /// but whose `capacity` or `T::size()` does not is an
+    /// [`io::ErrorKind::InvalidData`] error; any other file gets a fresh header
+    /// and `length` starts at zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the backing file.
+    /// * `capacity` - Maximum capacity of this storage engine.
+    pub(crate) fn open(path: &Path, capacity: usize) -> io::Result<Self> {
+        let (mem, length) = FileBacked::open(path, capacity, T::size())?;
+
+        Ok(Self {
+            mem,
+            length,
+            capacity,
+            reserved_capacity: capacity,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Block until every record and the header are durably on disk.
+    pub(crate) fn flush(&self) -> io::Result<()> {
+        self.mem.flush()
+    }
+
+    /// Begin flushing every record and the header to disk, without blocking.
+    pub(crate) fn flush_async(&self) -> io::Result<()> {
+        self.mem.flush_async()
     }
 }
 
 impl<T: Record> OnHeapStorage<T> {
-    /// Create a new instance of [`Storage`] engine backed by on-heap memory.
+    /// Create a new instance of [`Storage`] engine backed by on-heap memory,
+    /// using the [`Global`] allocator.
     ///
     /// # Panics
     ///
@@ -170,18 +679,58 @@ impl<T: Record> OnHeapStorage<T> {
     ///
     /// * `capacity` - Maximum capacity of this storage engine.
     pub(crate) fn new(capacity: usize) -> Self {
-        Self {
-            mem: OnHeap::alloc(capacity * T::size()),
+        Self::new_in(capacity, Global)
+    }
+
+    /// Fallible variant of [`OnHeapStorage::new`] that returns a [`TryReserveError`]
+    /// instead of aborting when the requested capacity could not be allocated.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum capacity of this storage engine.
+    pub(crate) fn try_new(capacity: usize) -> Result<Self, TryReserveError> {
+        Self::try_new_in(capacity, Global)
+    }
+}
+
+impl<T: Record, A: Allocator> OnHeapStorage<T, A> {
+    /// Create a new instance of [`Storage`] engine backed by on-heap memory
+    /// allocated using `alloc`.
+    ///
+    /// # Panics
+    ///
+    /// * Requested capacity could not be allocated.
+    /// * capacity == 0 or type is zero sized type.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum capacity of this storage engine.
+    /// * `alloc` - Allocator used to request memory for this storage engine.
+    pub(crate) fn new_in(capacity: usize, alloc: A) -> Self {
+        Self::try_new_in(capacity, alloc).expect("Cannot allocate capacity for OnHeapStorage")
+    }
+
+    /// Fallible variant of [`OnHeapStorage::new_in`] that returns a [`TryReserveError`]
+    /// instead of aborting when the requested capacity could not be allocated.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum capacity of this storage engine.
+    /// * `alloc` - Allocator used to request memory for this storage engine.
+    pub(crate) fn try_new_in(capacity: usize, alloc: A) -> Result<Self, TryReserveError> {
+        Ok(Self {
+            mem: OnHeap::try_alloc_in(capacity * T::size(), alloc)?,
             length: 0,
             capacity,
+            reserved_capacity: capacity,
             phantom: PhantomData,
-        }
+        })
     }
 }
 
 impl<T: Record, M> Storage for MemStorage<T, M>
 where
-    M: AsRef<[u8]> + AsMut<[u8]>,
+    M: AsRef<[u8]> + AsMut<[u8]> + TrackLength,
 {
     type Record = T;
 
@@ -213,10 +762,11 @@ where
             // Has to be memove rather than memcpy because we are copying overlapping
             // range of bytes. This generally requires memory to be copied in certain
             // direction, unlike memcpy that can arbitrarily copy bytes.
-            std::ptr::copy(src_ptr, dst_ptr, end_offset - offset);
+            core::ptr::copy(src_ptr, dst_ptr, end_offset - offset);
         }
 
         self.length -= len;
+        self.mem.set_length(self.length);
     }
 
     fn extend(&mut self, records: &[T]) {
@@ -235,14 +785,16 @@ where
             // Source and destination are guaranteed to be separate memory allocations,
             // meaning they don't share the same memory regions. So it's safe to use
             // memcpy here to copy bytes from source to destination.
-            std::ptr::copy_nonoverlapping(src_ptr, dst_ptr, src.len());
+            core::ptr::copy_nonoverlapping(src_ptr, dst_ptr, src.len());
         }
 
         self.length += records.len();
+        self.mem.set_length(self.length);
     }
 
     fn clear(&mut self) {
         self.length = 0;
+        self.mem.set_length(self.length);
     }
 
     fn records(&self) -> &[T] {
@@ -259,14 +811,79 @@ where
             T::from_bytes_slice(bytes)
         }
     }
+
+    fn get(&self, index: usize) -> T
+    where
+        T: Copy,
+    {
+        let offset = index * T::size();
+
+        // Safety: Invariant; index < self.length, so this falls within bytes a
+        // prior `extend` already initialized.
+        unsafe {
+            let mem = self.mem.as_ref();
+            *T::from_bytes(&mem[offset..offset + T::size()])
+        }
+    }
+
+    fn set(&mut self, index: usize, record: T) {
+        let offset = index * T::size();
+        let src = T::to_bytes(&record);
+
+        // Safety: Invariant; index < self.length, so this falls within memory
+        // this Storage owns.
+        unsafe {
+            let mem = self.mem.as_mut();
+            core::ptr::copy_nonoverlapping(src.as_ptr(), mem.as_mut_ptr().add(offset), src.len());
+        }
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.length = len;
+        self.mem.set_length(self.length);
+    }
+
+    fn try_grow(&mut self, additional: usize) -> bool {
+        self.grow(additional)
+    }
+}
+
+/// Page class requested for an off-heap memory mapping.
+///
+/// Larger pages cut TLB pressure when scanning large mappings (the
+/// benchmarks run with `CAPACITY` up to 512 MiB), at the cost of needing the
+/// system to actually have pages of that size available.
+#[cfg(feature = "mmap")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HugePageSize {
+    /// Default 4 KiB pages.
+    #[default]
+    None,
+
+    /// Opportunistically back the mapping with transparent huge pages via
+    /// `madvise(MADV_HUGEPAGE)` on Linux; a no-op on other platforms. Unlike
+    /// [`HugePageSize::Explicit`], this never fails: the kernel may or may
+    /// not actually promote the mapping's pages.
+    Transparent,
+
+    /// Explicit hugetlb mapping at the given page shift, e.g. `21` for 2 MiB
+    /// or `30` for 1 GiB pages. Requires the system to have huge pages of
+    /// that size already reserved (e.g. via `/proc/sys/vm/nr_hugepages`);
+    /// falls back to [`HugePageSize::None`] if the reservation isn't available.
+    Explicit(u8),
 }
 
 /// Off heap memory that backs a [`MemStorage`] engine.
+#[cfg(feature = "mmap")]
 #[derive(Debug)]
-pub struct OffHeap(MmapMut);
+pub struct OffHeap {
+    mmap: MmapMut,
+    huge_pages: HugePageSize,
+}
 
+#[cfg(feature = "mmap")]
 impl OffHeap {
-    /// Allocate some number of bytes on heap.
+    /// Allocate some number of bytes on heap, using default 4 KiB pages.
     ///
     /// * Frees memory using RAII pattern, so no method to deallocate memory.
     /// * If successful memory is guaranteed to be page aligned.
@@ -275,42 +892,453 @@ impl OffHeap {
     ///
     /// * `capacity` - Maximum number of bytes to allocate.
     fn alloc(capacity: usize) -> Self {
-        let mmap = MmapOptions::new()
-            // .huge(None) TODO: Enable support for huge pages.
+        Self::alloc_with(capacity, HugePageSize::None)
+    }
+
+    /// Fallible variant of [`OffHeap::alloc`] that returns a [`TryReserveError`]
+    /// instead of aborting when the requested mapping could not be created.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum number of bytes to allocate.
+    fn try_alloc(capacity: usize) -> Result<Self, TryReserveError> {
+        Self::try_alloc_with(capacity, HugePageSize::None)
+    }
+
+    /// Allocate some number of bytes on heap, requesting `huge_pages`.
+    ///
+    /// * Frees memory using RAII pattern, so no method to deallocate memory.
+    /// * If successful memory is guaranteed to be page aligned.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum number of bytes to allocate.
+    /// * `huge_pages` - Page class to request for the mapping.
+    fn alloc_with(capacity: usize, huge_pages: HugePageSize) -> Self {
+        Self::try_alloc_with(capacity, huge_pages).expect("Cannot allocate anonymous mmap")
+    }
+
+    /// Fallible variant of [`OffHeap::alloc_with`] that returns a [`TryReserveError`]
+    /// instead of aborting when the requested mapping could not be created.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum number of bytes to allocate.
+    /// * `huge_pages` - Page class to request for the mapping.
+    fn try_alloc_with(capacity: usize, huge_pages: HugePageSize) -> Result<Self, TryReserveError> {
+        Self::try_map_with(capacity, huge_pages, Self::map_normal)
+    }
+
+    /// Reserve `reserved` bytes of virtual address space up front, requesting
+    /// `huge_pages`, without eagerly faulting them in.
+    ///
+    /// Unlike [`OffHeap::try_alloc_with`], this skips `.populate()` (a granted
+    /// [`HugePageSize::Explicit`] mapping is still populated): the kernel commits physical pages lazily, on first touch, so a later grow
+    /// of the logical capacity is free as long as it stays within `reserved`.
+    ///
+    /// # Arguments
+    ///
+    /// * `reserved` - Total number of bytes to reserve address space for.
+    /// * `huge_pages` - Page class to request for the mapping.
+    fn try_reserve_with(reserved: usize, huge_pages: HugePageSize) -> Result<Self, TryReserveError> {
+        Self::try_map_with(reserved, huge_pages, Self::map_reserved)
+    }
+
+    /// Shared mapping logic for [`OffHeap::try_alloc_with`]/[`OffHeap::try_reserve_with`],
+    /// parameterized over how the non-huge-page fallback maps its bytes.
+    fn try_map_with(
+        capacity: usize,
+        huge_pages: HugePageSize,
+        map: impl Fn(usize) -> Result<MmapMut, TryReserveError>,
+    ) -> Result<Self, TryReserveError> {
+        let (mmap, granted) = match huge_pages {
+            HugePageSize::Explicit(shift) => {
+                let page = 1usize << shift;
+                let rounded = capacity.div_ceil(page) * page;
+
+                match MmapOptions::new()
+                    .len(rounded)
+                    .huge(Some(shift))
+                    .populate()
+                    .map_anon()
+                {
+                    Ok(mmap) => (mmap, huge_pages),
+                    // Explicit hugetlb mappings fail when the system hasn't
+                    // reserved pages of that size; fall back rather than panic.
+                    Err(_) => (map(capacity)?, HugePageSize::None),
+                }
+            }
+
+            HugePageSize::None | HugePageSize::Transparent => (map(capacity)?, huge_pages),
+        };
+
+        if granted == HugePageSize::Transparent {
+            Self::advise_transparent(&mmap);
+        }
+
+        Ok(Self {
+            mmap,
+            huge_pages: granted,
+        })
+    }
+
+    /// Map `capacity` bytes of ordinary, eagerly-faulted anonymous memory.
+    fn map_normal(capacity: usize) -> Result<MmapMut, TryReserveError> {
+        MmapOptions::new()
             .len(capacity)
             // Fault all pages so that they are eagerly initialized.
             .populate()
             // Map with anonymous memory map for off-heap memory.
             .map_anon()
-            // Especially with huge pages.
-            .expect("Cannot allocate anonymous mmap");
+            .map_err(|_| TryReserveError::AllocError)
+    }
+
+    /// Map `capacity` bytes of anonymous memory reserved up front but not
+    /// eagerly faulted, so physical pages are committed lazily by the kernel
+    /// only as the mapping is actually touched.
+    fn map_reserved(capacity: usize) -> Result<MmapMut, TryReserveError> {
+        MmapOptions::new()
+            .len(capacity)
+            .map_anon()
+            .map_err(|_| TryReserveError::AllocError)
+    }
 
-        Self(mmap)
+    /// Opt `mmap` into transparent huge pages on platforms that support it.
+    #[cfg(target_os = "linux")]
+    fn advise_transparent(mmap: &MmapMut) {
+        // Safety: `mmap` is a valid mapping of its own length for the
+        // lifetime of this call; advice failures are non-fatal and ignored,
+        // same as any other `madvise` hint.
+        unsafe {
+            libc::madvise(
+                mmap.as_ptr() as *mut libc::c_void,
+                mmap.len(),
+                libc::MADV_HUGEPAGE,
+            );
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn advise_transparent(_mmap: &MmapMut) {}
+
+    /// Page class actually granted to this mapping; may fall short of what
+    /// was requested (see [`HugePageSize::Explicit`]).
+    pub(crate) fn huge_page_size(&self) -> HugePageSize {
+        self.huge_pages
     }
 }
 
+#[cfg(feature = "mmap")]
 impl AsRef<[u8]> for OffHeap {
     fn as_ref(&self) -> &[u8] {
-        &self.0
+        &self.mmap
     }
 }
 
+#[cfg(feature = "mmap")]
 impl AsMut<[u8]> for OffHeap {
     fn as_mut(&mut self) -> &mut [u8] {
-        &mut self.0
+        &mut self.mmap
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl TrackLength for OffHeap {}
+
+/// Page size of the host, used to round [`MagicStorage`]'s region up to whole pages so
+/// it can be mapped twice in a row.
+#[cfg(all(feature = "mmap", target_os = "linux"))]
+fn page_size() -> usize {
+    // Safety: querying `_SC_PAGESIZE` never fails.
+    unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize }
+}
+
+#[cfg(all(feature = "mmap", target_os = "linux"))]
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// Smallest byte count that is a multiple of both `page` and `record_size`, so
+/// [`MagicStorage`]'s region is simultaneously mappable in whole pages and holds a
+/// whole number of records, leaving no gap before the mirror seam.
+#[cfg(all(feature = "mmap", target_os = "linux"))]
+fn lcm(page: usize, record_size: usize) -> usize {
+    page / gcd(page, record_size) * record_size
+}
+
+/// Storage engine backed by a "magic" / double-mapped ring buffer: the same physical
+/// pages are mapped twice in a row in virtual memory, so a read or write that runs off
+/// the end of the first copy transparently lands on the second copy instead of needing
+/// to wrap to a different address.
+///
+/// This gives [`MagicStorage::trim`] the same O(1) `head = (head + n) % capacity` as
+/// [`RingStorage`], but unlike [`RingStorage`] -- which still has to fall back to
+/// [`Storage::records_slices`] once it wraps -- [`MagicStorage::records`] can keep
+/// handing back a single contiguous `&[T]` slice even when the live records straddle
+/// the seam between the two copies, because the bytes there are the same bytes as the
+/// start of the buffer.
+///
+/// # Invariant
+///
+/// Only whole pages can be mirrored, so `capacity * T::size()` is rounded up to a
+/// multiple of both the host page size and `T::size()` at construction -- not just
+/// the page size, or a record could straddle the gap left before the mirror seam --
+/// [`MagicStorage::capacity`] reports this rounded-up value, which may be slightly
+/// larger than what was requested.
+///
+/// # Platform
+///
+/// Requires `memfd_create`, so this is Linux-only for now; see [`OffHeapStorage`] for a
+/// portable (but O(n)-trim) off-heap alternative.
+#[cfg(all(feature = "mmap", target_os = "linux"))]
+pub struct MagicStorage<T> {
+    /// Base address of the `2 * region` byte reservation.
+    base: NonNull<u8>,
+    /// Size, in bytes, of one copy of the mirrored region (a multiple of the page size).
+    region: usize,
+    /// Record index of the oldest live record.
+    head: usize,
+    /// Number of live records.
+    length: usize,
+    /// Usable capacity, in records; `region / T::size()`.
+    capacity: usize,
+    phantom: PhantomData<T>,
+}
+
+#[cfg(all(feature = "mmap", target_os = "linux"))]
+impl<T: Record> MagicStorage<T> {
+    /// Create a new instance of [`Storage`] engine backed by a double-mapped ring buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the double mapping could not be established.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Minimum capacity of this storage engine; rounded up to a whole
+    ///   number of pages, see the type-level invariant.
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self::try_new(capacity).expect("Cannot double-map memory for MagicStorage")
+    }
+
+    /// Fallible variant of [`MagicStorage::new`] that returns a [`TryReserveError`]
+    /// instead of aborting when the double mapping could not be established.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Minimum capacity of this storage engine; rounded up to a whole
+    ///   number of pages, see the type-level invariant.
+    pub(crate) fn try_new(capacity: usize) -> Result<Self, TryReserveError> {
+        // Rounding to a multiple of the page size alone isn't enough: if `T::size()`
+        // doesn't divide the page size, flooring `region / T::size()` for `capacity`
+        // would leave a gap of leftover bytes before the mirror seam, and a wrapped
+        // `records()` read spanning that gap would return misaligned data. Round up
+        // to a multiple of both instead, so `region` is exactly `capacity * T::size()`.
+        let unit = lcm(page_size(), T::size());
+        let region = (capacity * T::size()).div_ceil(unit) * unit;
+        let capacity = region / T::size();
+        debug_assert_eq!(capacity * T::size(), region);
+
+        // Safety: the name is a valid nul-terminated C string; `memfd_create` either
+        // returns a fresh, owned file descriptor or `-1` on error.
+        let fd = unsafe { libc::memfd_create(c"crosstream-magic-ring".as_ptr(), 0) };
+        if fd < 0 {
+            return Err(TryReserveError::AllocError);
+        }
+
+        // Safety: `fd` was just created above and isn't shared with anything else yet.
+        if unsafe { libc::ftruncate(fd, region as libc::off_t) } < 0 {
+            unsafe { libc::close(fd) };
+            return Err(TryReserveError::AllocError);
+        }
+
+        // Reserve a stable `2 * region` byte range of address space up front, so the
+        // two mappings below are guaranteed to land back-to-back.
+        //
+        // Safety: requesting a `PROT_NONE` anonymous mapping never touches existing
+        // memory; `addr == null` lets the kernel pick the base address.
+        let base = unsafe {
+            libc::mmap(
+                core::ptr::null_mut(),
+                region * 2,
+                libc::PROT_NONE,
+                libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+                -1,
+                0,
+            )
+        };
+        if base == libc::MAP_FAILED {
+            unsafe { libc::close(fd) };
+            return Err(TryReserveError::AllocError);
+        }
+
+        // Map the same underlying file object into both halves of the reservation, so
+        // they mirror each other; `MAP_FIXED` is safe here because `base`/`base +
+        // region` fall entirely within the reservation just made above.
+        //
+        // Safety: `fd` has length `region`, and both target ranges were reserved above.
+        let first = unsafe {
+            libc::mmap(
+                base,
+                region,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED | libc::MAP_FIXED,
+                fd,
+                0,
+            )
+        };
+        let second = if first == libc::MAP_FAILED {
+            libc::MAP_FAILED
+        } else {
+            // Safety: same as the first mapping, offset by `region` bytes.
+            unsafe {
+                libc::mmap(
+                    base.cast::<u8>().add(region).cast(),
+                    region,
+                    libc::PROT_READ | libc::PROT_WRITE,
+                    libc::MAP_SHARED | libc::MAP_FIXED,
+                    fd,
+                    0,
+                )
+            }
+        };
+
+        // Safety: both mappings (if established) keep the file alive; the descriptor
+        // itself is no longer needed.
+        unsafe { libc::close(fd) };
+
+        if first == libc::MAP_FAILED || second == libc::MAP_FAILED {
+            // Safety: `base`/`region * 2` is the exact reservation made above.
+            unsafe { libc::munmap(base, region * 2) };
+            return Err(TryReserveError::AllocError);
+        }
+
+        Ok(Self {
+            // Safety: a successful `mmap` never returns a null base address.
+            base: unsafe { NonNull::new_unchecked(base.cast()) },
+            region,
+            head: 0,
+            length: 0,
+            capacity,
+            phantom: PhantomData,
+        })
+    }
+
+    /// View the full `2 * region` byte mirrored mapping as bytes.
+    fn as_bytes(&self) -> &[u8] {
+        // Safety: `base` is a valid mapping of `2 * region` bytes for the lifetime of `self`.
+        unsafe { slice::from_raw_parts(self.base.as_ptr(), self.region * 2) }
+    }
+
+    /// Mutably view the full `2 * region` byte mirrored mapping as bytes.
+    fn as_bytes_mut(&mut self) -> &mut [u8] {
+        // Safety: `base` is a valid mapping of `2 * region` bytes for the lifetime of `self`.
+        unsafe { slice::from_raw_parts_mut(self.base.as_ptr(), self.region * 2) }
+    }
+}
+
+#[cfg(all(feature = "mmap", target_os = "linux"))]
+impl<T: Record + Copy> Storage for MagicStorage<T> {
+    type Record = T;
+
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn length(&self) -> usize {
+        self.length
+    }
+
+    fn remaining(&self) -> usize {
+        self.capacity - self.length
+    }
+
+    fn trim(&mut self, len: usize) {
+        self.head = (self.head + len) % self.capacity;
+        self.length -= len;
+    }
+
+    fn extend(&mut self, records: &[T]) {
+        let offset = (self.head + self.length) % self.capacity * T::size();
+        let src = T::to_bytes_slice(records);
+
+        // Safety: the mirror means a write starting anywhere in the first copy and
+        // running for up to `region` bytes always lands on mapped memory, whether or
+        // not it crosses the seam between the two copies.
+        unsafe {
+            let dst = self.as_bytes_mut().as_mut_ptr().add(offset);
+            core::ptr::copy_nonoverlapping(src.as_ptr(), dst, src.len());
+        }
+
+        self.length += records.len();
+    }
+
+    fn clear(&mut self) {
+        self.head = 0;
+        self.length = 0;
+    }
+
+    fn records(&self) -> &[T] {
+        let offset = self.head * T::size();
+        let bytes = &self.as_bytes()[offset..offset + self.length * T::size()];
+        T::from_bytes_slice(bytes)
+    }
+
+    fn get(&self, index: usize) -> T
+    where
+        T: Copy,
+    {
+        let offset = (self.head + index) % self.capacity * T::size();
+        *T::from_bytes(&self.as_bytes()[offset..offset + T::size()])
+    }
+
+    fn set(&mut self, index: usize, record: T) {
+        let offset = (self.head + index) % self.capacity * T::size();
+        let src = T::to_bytes(&record);
+        self.as_bytes_mut()[offset..offset + T::size()].copy_from_slice(src);
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.length = len;
+    }
+}
+
+#[cfg(all(feature = "mmap", target_os = "linux"))]
+impl<T> Drop for MagicStorage<T> {
+    fn drop(&mut self) {
+        // Safety: `base`/`region * 2` is the exact mapping established in `try_new`.
+        unsafe {
+            libc::munmap(self.base.as_ptr().cast(), self.region * 2);
+        }
+    }
+}
+
+#[cfg(all(feature = "mmap", target_os = "linux"))]
+impl<T> core::fmt::Debug for MagicStorage<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MagicStorage")
+            .field("head", &self.head)
+            .field("length", &self.length)
+            .field("capacity", &self.capacity)
+            .finish()
     }
 }
 
 /// On heap memory that backs a [`MemStorage`] engine.
+///
+/// Generic over the [`Allocator`] used to request memory, defaulting to the
+/// [`Global`] allocator. Custom allocators are threaded through via the
+/// `_in` constructors on [`OnHeapStorage`] and `OnHeapSegment`.
 #[derive(Debug)]
-pub struct OnHeap {
-    ptr: *mut u8,
+pub struct OnHeap<A: Allocator = Global> {
+    ptr: NonNull<u8>,
     layout: Layout,
     len: usize,
+    alloc: A,
 }
 
-impl OnHeap {
-    /// Allocate some number of bytes on heap.
+impl OnHeap<Global> {
+    /// Allocate some number of bytes on heap, using the [`Global`] allocator.
     ///
     /// Frees memory using RAII pattern, so no method to deallocate memory.
     ///
@@ -321,59 +1349,222 @@ impl OnHeap {
     ///
     /// * `capacity` - Maximum number of bytes to allocate.
     fn alloc(capacity: usize) -> Self {
+        Self::alloc_in(capacity, Global)
+    }
+
+    /// Fallible variant of [`OnHeap::alloc`] that returns a [`TryReserveError`]
+    /// instead of aborting when the requested capacity could not be allocated.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum number of bytes to allocate.
+    fn try_alloc(capacity: usize) -> Result<Self, TryReserveError> {
+        Self::try_alloc_in(capacity, Global)
+    }
+}
+
+impl<A: Allocator> OnHeap<A> {
+    /// Allocate some number of bytes on heap, using `alloc`.
+    ///
+    /// Frees memory using RAII pattern, so no method to deallocate memory.
+    ///
+    /// Note, if successful we might over allocate, i.e contain more bytes than
+    /// requested. But this will never be visible outside of this container.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum number of bytes to allocate.
+    /// * `alloc` - Allocator used to request memory.
+    fn alloc_in(capacity: usize, alloc: A) -> Self {
+        Self::try_alloc_in(capacity, alloc).expect("Cannot allocate requested memory")
+    }
+
+    /// Fallible variant of [`OnHeap::alloc_in`] that returns a [`TryReserveError`]
+    /// instead of aborting when the requested capacity could not be allocated.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum number of bytes to allocate.
+    /// * `alloc` - Allocator used to request memory.
+    fn try_alloc_in(capacity: usize, alloc: A) -> Result<Self, TryReserveError> {
         // Layout the describes the allocation requirements.
         let align = align_of::<u8>();
         let layout = Layout::from_size_align(capacity, align)
-            .expect("Cannot create a layout for global allocator");
-
-        // Safety
-        // 1. We are properly aligning memory (which should be 1).
-        // 2. Size of allocation must be > 0 (cannot create layout otherwise).
-        let ptr = unsafe {
-            // Allocate memory.
-            let ptr = alloc::alloc(layout);
-
-            // If allocation was unsuccessful.
-            if ptr.is_null() {
-                handle_alloc_error(layout);
-            }
+            .map_err(|_| TryReserveError::CapacityOverflow)?;
 
-            // Return pointer to the newly allocated memory.
-            // This is now guaranteed to be non-null.
-            ptr
-        };
+        // Allocator reports a null/dangling allocation as an error rather
+        // than aborting the process, so we can just propagate it.
+        let ptr = alloc
+            .allocate(layout)
+            .map_err(|_| TryReserveError::AllocError)?
+            .cast::<u8>();
 
-        Self {
+        Ok(Self {
             ptr,
             layout,
             len: capacity,
-        }
+            alloc,
+        })
     }
 }
 
-impl Drop for OnHeap {
+impl<A: Allocator> Drop for OnHeap<A> {
     fn drop(&mut self) {
-        // Cannot initialize with invalid pointer and layout.
+        // Safety: ptr/layout pair is the exact pair handed back by `alloc.allocate`.
         unsafe {
-            alloc::dealloc(self.ptr, self.layout);
+            self.alloc.deallocate(self.ptr, self.layout);
         }
     }
 }
 
-impl AsRef<[u8]> for OnHeap {
+impl<A: Allocator> AsRef<[u8]> for OnHeap<A> {
     fn as_ref(&self) -> &[u8] {
         // Safety
         // * Pointer is guaranteed to be initialized.
         // * length is guaranteed to be > 0.
-        unsafe { slice::from_raw_parts(self.ptr, self.len) }
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
     }
 }
 
-impl AsMut<[u8]> for OnHeap {
+impl<A: Allocator> AsMut<[u8]> for OnHeap<A> {
     fn as_mut(&mut self) -> &mut [u8] {
         // Safety
         // * Pointer is guaranteed to be initialized.
         // * length is guaranteed to be > 0.
-        unsafe { slice::from_raw_parts_mut(self.ptr, self.len) }
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<A: Allocator> TrackLength for OnHeap<A> {}
+
+/// File-backed memory that backs a [`MemStorage`] engine, so its records
+/// survive a process restart.
+///
+/// A fixed 32 byte header (magic, `T::size()`, `capacity`, and the current
+/// `length`) is mapped at offset 0, with record bytes following it; record
+/// bytes are written straight into the mapping as they're appended, but are
+/// only guaranteed durable once [`FileBacked::flush`]/[`FileBacked::flush_async`]
+/// returns, same as any other [`MmapMut`].
+#[cfg(feature = "mmap")]
+#[derive(Debug)]
+pub struct FileBacked {
+    mmap: MmapMut,
+}
+
+/// Identifies a file as holding a [`FileBacked`] header in the format below,
+/// so an unrelated file (or a stale header layout) is rejected rather than
+/// silently misread.
+#[cfg(feature = "mmap")]
+const FILE_BACKED_MAGIC: &[u8; 8] = b"XSTREAM1";
+
+/// Byte offsets of each header field within the mapping.
+#[cfg(feature = "mmap")]
+const RECORD_SIZE_OFFSET: usize = 8;
+#[cfg(feature = "mmap")]
+const CAPACITY_OFFSET: usize = 16;
+#[cfg(feature = "mmap")]
+const LENGTH_OFFSET: usize = 24;
+
+/// Total size, in bytes, of the header described above.
+#[cfg(feature = "mmap")]
+const HEADER_SIZE: usize = 32;
+
+#[cfg(feature = "mmap")]
+impl FileBacked {
+    /// Open (creating if needed) a file-backed mapping for `capacity` records
+    /// of `record_size` bytes at `path`.
+    ///
+    /// If `path` already holds a header whose magic, `record_size`, and
+    /// `capacity` all match, the persisted `length` is returned alongside the
+    /// mapping. If the magic matches but `record_size` or `capacity` does not,
+    /// this returns an [`io::ErrorKind::InvalidData`] error. Without the magic,
+    /// a fresh header is written with `length` of `0`, after the file is sized
+    /// for `capacity` records if it was empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the backing file.
+    /// * `capacity` - Maximum number of records this mapping can hold.
+    /// * `record_size` - Size in bytes of a single record.
+    fn open(path: &Path, capacity: usize, record_size: usize) -> io::Result<(Self, usize)> {
+        let file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .write(true)
+            .open(path)?;
+
+        let total_len = HEADER_SIZE + capacity * record_size;
+        if file.metadata()?.len() == 0 {
+            file.set_len(total_len as u64)?;
+        }
+
+        // Safety: `file` stays open for the lifetime of the mapping and isn't
+        // truncated by any other code path in this crate.
+        let mut mmap = unsafe { MmapOptions::new().len(total_len).map_mut(&file)? };
+
+        let length = if &mmap[..8] == FILE_BACKED_MAGIC {
+            let found_record_size = read_u64(&mmap, RECORD_SIZE_OFFSET) as usize;
+            let found_capacity = read_u64(&mmap, CAPACITY_OFFSET) as usize;
+
+            if found_record_size != record_size || found_capacity != capacity {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "file-backed mapping header does not match this record type/capacity",
+                ));
+            }
+
+            read_u64(&mmap, LENGTH_OFFSET) as usize
+        } else {
+            mmap[..8].copy_from_slice(FILE_BACKED_MAGIC);
+            write_u64(&mut mmap, RECORD_SIZE_OFFSET, record_size as u64);
+            write_u64(&mut mmap, CAPACITY_OFFSET, capacity as u64);
+            write_u64(&mut mmap, LENGTH_OFFSET, 0);
+            0
+        };
+
+        Ok((Self { mmap }, length))
+    }
+
+    /// Block until every record and the header are durably on disk.
+    fn flush(&self) -> io::Result<()> {
+        self.mmap.flush()
+    }
+
+    /// Begin flushing every record and the header to disk, without blocking.
+    fn flush_async(&self) -> io::Result<()> {
+        self.mmap.flush_async()
+    }
+}
+
+/// Read a little-endian `u64` header field starting at `offset`.
+#[cfg(feature = "mmap")]
+fn read_u64(mem: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(mem[offset..offset + 8].try_into().unwrap())
+}
+
+/// Write a little-endian `u64` header field starting at `offset`.
+#[cfg(feature = "mmap")]
+fn write_u64(mem: &mut [u8], offset: usize, value: u64) {
+    mem[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+}
+
+#[cfg(feature = "mmap")]
+impl AsRef<[u8]> for FileBacked {
+    fn as_ref(&self) -> &[u8] {
+        &self.mmap[HEADER_SIZE..]
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl AsMut<[u8]> for FileBacked {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.mmap[HEADER_SIZE..]
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl TrackLength for FileBacked {
+    fn set_length(&mut self, length: usize) {
+        write_u64(&mut self.mmap, LENGTH_OFFSET, length as u64);
     }
 }
@@ -0,0 +1,270 @@
+//! Single-producer/single-consumer storage with atomic counters, gated behind the `sync` feature.
+//!
+//! The crate is unconditionally single-threaded otherwise, so [`AtomicMemStorage`] is
+//! purely additive: the default [`MemStorage`](crate::MemStorage) keeps its plain
+//! `usize` fields and pays no atomic overhead, and only code built with `sync` sees
+//! this module at all.
+
+#[cfg(feature = "mmap")]
+use crate::{HugePageSize, OffHeap};
+use crate::{OnHeap, Record, Storage, TryReserveError};
+use allocator_api2::alloc::Global;
+use std::cmp::min;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Type alias for [`AtomicMemStorage`] backed by [`OffHeap`] memory.
+#[cfg(feature = "mmap")]
+pub type AtomicOffHeapStorage<T> = AtomicMemStorage<T, OffHeap>;
+
+/// Type alias for [`AtomicMemStorage`] backed by [`OnHeap`] memory, using the
+/// [`Global`] allocator.
+pub type AtomicOnHeapStorage<T> = AtomicMemStorage<T, OnHeap<Global>>;
+
+/// A [`Storage`] engine laid out for one producer and one consumer: appends via
+/// [`Storage::extend`] only move `length`, discards from the front via
+/// [`Storage::trim`] only move `head`, and there is no lock. Every method that
+/// changes it takes `&mut self` and this type hands out no separate producer or
+/// consumer handle, so it does not by itself let two threads use one instance
+/// at the same time.
+///
+/// Physically this is a circular buffer, same layout as [`RingStorage`](crate::RingStorage),
+/// except `head` (the trim offset the consumer owns) and `length` (the publish
+/// offset the producer owns) are each a single [`AtomicUsize`] instead of plain
+/// fields:
+///
+/// * [`Storage::extend`] copies record bytes into place first, then publishes
+///   them with a `Release` store to `length` -- so a consumer's `Acquire` load
+///   of `length` can never observe a record whose bytes are still mid-copy.
+/// * [`Storage::trim`] only ever advances `head`, which the producer never
+///   reads with anything stronger than is needed to compute `remaining()`, so
+///   the two sides never need to synchronize beyond these two atomics.
+///
+/// Both counters are monotonically increasing lifetime counts (never wrapped
+/// to `capacity` themselves); the physical slot for lifetime index `i` is
+/// `i % capacity`, same trick used by most lock-free SPSC ring buffers to make
+/// "empty" (`head == length`) unambiguous from "full".
+#[derive(Debug)]
+pub struct AtomicMemStorage<T, M> {
+    mem: M,
+    head: AtomicUsize,
+    length: AtomicUsize,
+    capacity: usize,
+    phantom: PhantomData<T>,
+}
+
+#[cfg(feature = "mmap")]
+impl<T: Record> AtomicOffHeapStorage<T> {
+    /// Create a new instance of [`Storage`] engine backed by off-heap memory,
+    /// ready to be split across a producer and a consumer thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if requested capacity could not be allocated.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum capacity of this storage engine.
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self::try_new(capacity).expect("Cannot allocate capacity for AtomicOffHeapStorage")
+    }
+
+    /// Fallible variant of [`AtomicOffHeapStorage::new`] that returns a
+    /// [`TryReserveError`] instead of aborting when the requested capacity
+    /// could not be allocated.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum capacity of this storage engine.
+    pub(crate) fn try_new(capacity: usize) -> Result<Self, TryReserveError> {
+        Ok(Self {
+            mem: OffHeap::try_alloc_with(capacity * T::size(), HugePageSize::None)?,
+            head: AtomicUsize::new(0),
+            length: AtomicUsize::new(0),
+            capacity,
+            phantom: PhantomData,
+        })
+    }
+}
+
+impl<T: Record> AtomicOnHeapStorage<T> {
+    /// Create a new instance of [`Storage`] engine backed by on-heap memory,
+    /// using the [`Global`] allocator, ready to be split across a producer
+    /// and a consumer thread.
+    ///
+    /// * TODO: Add support for a custom `Allocator`, mirroring [`OnHeapStorage`](crate::OnHeapStorage).
+    ///
+    /// # Panics
+    ///
+    /// Panics if requested capacity could not be allocated.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum capacity of this storage engine.
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self::try_new(capacity).expect("Cannot allocate capacity for AtomicOnHeapStorage")
+    }
+
+    /// Fallible variant of [`AtomicOnHeapStorage::new`] that returns a
+    /// [`TryReserveError`] instead of aborting when the requested capacity
+    /// could not be allocated.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum capacity of this storage engine.
+    pub(crate) fn try_new(capacity: usize) -> Result<Self, TryReserveError> {
+        Ok(Self {
+            mem: OnHeap::try_alloc_in(capacity * T::size(), Global)?,
+            head: AtomicUsize::new(0),
+            length: AtomicUsize::new(0),
+            capacity,
+            phantom: PhantomData,
+        })
+    }
+}
+
+impl<T, M> AtomicMemStorage<T, M> {
+    /// Physical, lifetime-wrapped slot for the absolute record index `i`.
+    fn physical(&self, i: usize) -> usize {
+        i % self.capacity.max(1)
+    }
+}
+
+impl<T: Record, M> Storage for AtomicMemStorage<T, M>
+where
+    M: AsRef<[u8]> + AsMut<[u8]>,
+{
+    type Record = T;
+
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn length(&self) -> usize {
+        // Acquire pairs with the Release store at the end of `extend`, so a
+        // caller that just observed this length can safely read that many
+        // records via `records`/`records_slices`.
+        let length = self.length.load(Ordering::Acquire);
+        let head = self.head.load(Ordering::Acquire);
+        length - head
+    }
+
+    fn remaining(&self) -> usize {
+        self.capacity - self.length()
+    }
+
+    fn trim(&mut self, len: usize) {
+        // Consumer-owned: no other thread ever advances `head`, so Relaxed is
+        // enough to read our own prior value back.
+        let head = self.head.load(Ordering::Relaxed);
+
+        // Release so a producer computing `remaining()` after this observes
+        // the reclaimed space.
+        self.head.store(head + len, Ordering::Release);
+    }
+
+    fn extend(&mut self, records: &[T]) {
+        let capacity = self.capacity;
+        // Producer-owned: no other thread ever advances `length`.
+        let length = self.length.load(Ordering::Relaxed);
+        let tail = self.physical(length);
+
+        let first_len = min(records.len(), capacity - tail);
+        let (first, second) = records.split_at(first_len);
+
+        // Safety: Invariant; records.len() <= self.remaining(), so both runs
+        // land within bytes this storage owns and neither overlaps the live
+        // region the consumer is reading.
+        unsafe {
+            let mem = self.mem.as_mut();
+
+            let src = T::to_bytes_slice(first);
+            std::ptr::copy_nonoverlapping(src.as_ptr(), mem.as_mut_ptr().add(tail * T::size()), src.len());
+
+            let src = T::to_bytes_slice(second);
+            std::ptr::copy_nonoverlapping(src.as_ptr(), mem.as_mut_ptr(), src.len());
+        }
+
+        // Publish only after every byte above has landed -- this Release
+        // store is what makes a consumer's Acquire load of `length` safe.
+        self.length.store(length + records.len(), Ordering::Release);
+    }
+
+    fn clear(&mut self) {
+        self.head.store(0, Ordering::Release);
+        self.length.store(0, Ordering::Release);
+    }
+
+    fn records(&self) -> &[T] {
+        let (first, second) = self.records_slices();
+        assert!(
+            second.is_empty(),
+            "AtomicMemStorage::records() cannot be used once records have wrapped; use records_slices() instead"
+        );
+        first
+    }
+
+    fn records_slices(&self) -> (&[T], &[T]) {
+        let head = self.head.load(Ordering::Acquire);
+        // Acquire pairs with `extend`'s Release store.
+        let length = self.length.load(Ordering::Acquire);
+        let live = length - head;
+
+        if live == 0 {
+            return (&[], &[]);
+        }
+
+        let capacity = self.capacity;
+        let physical_head = self.physical(head);
+
+        // Safety: The Acquire load above happens-after the producer's
+        // Release store, so every byte in the range below has already landed.
+        unsafe {
+            let mem = self.mem.as_ref();
+
+            if physical_head + live <= capacity {
+                let start = physical_head * T::size();
+                let end = (physical_head + live) * T::size();
+                (T::from_bytes_slice(&mem[start..end]), &[])
+            } else {
+                let first_len = capacity - physical_head;
+                let first = T::from_bytes_slice(&mem[physical_head * T::size()..capacity * T::size()]);
+                let second = T::from_bytes_slice(&mem[..(live - first_len) * T::size()]);
+                (first, second)
+            }
+        }
+    }
+
+    fn get(&self, index: usize) -> T
+    where
+        T: Copy,
+    {
+        let head = self.head.load(Ordering::Acquire);
+        let physical = self.physical(head + index);
+
+        // Safety: index < self.length(), which the Acquire load above
+        // guarantees is visible.
+        unsafe {
+            let mem = self.mem.as_ref();
+            *T::from_bytes(&mem[physical * T::size()..(physical + 1) * T::size()])
+        }
+    }
+
+    fn set(&mut self, index: usize, record: T) {
+        let head = self.head.load(Ordering::Relaxed);
+        let physical = self.physical(head + index);
+        let src = T::to_bytes(&record);
+
+        // Safety: Invariant; index < self.length(), so this falls within
+        // memory this storage owns.
+        unsafe {
+            let mem = self.mem.as_mut();
+            std::ptr::copy_nonoverlapping(src.as_ptr(), mem.as_mut_ptr().add(physical * T::size()), src.len());
+        }
+    }
+
+    fn truncate(&mut self, len: usize) {
+        let head = self.head.load(Ordering::Relaxed);
+        self.length.store(head + len, Ordering::Release);
+    }
+}
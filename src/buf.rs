@@ -1,6 +1,6 @@
 //! Definition of reusable buffer that can hold sequenced records.
 
-use crate::{OnHeapStorage, SeqRecord, Storage};
+use crate::{OnHeapStorage, SeqRecord, SharedRecords, Storage, Take};
 
 /// A reusable buffer to query from ring buffers.
 #[derive(Debug)]
@@ -36,6 +36,15 @@ impl<T: SeqRecord> QueryBuf<T> {
         self.0.records()
     }
 
+    /// View over at most `n` of this buffer's records, without copying.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Maximum number of records the returned view exposes.
+    pub fn take(&self, n: usize) -> Take<'_, T> {
+        Take::new(self.records(), n)
+    }
+
     /// Number of records that can be appended without overflow.
     #[allow(dead_code)]
     pub(crate) fn remaining(&self) -> usize {
@@ -64,6 +73,15 @@ impl<T: SeqRecord> QueryBuf<T> {
     }
 }
 
+impl<T: SeqRecord + Copy> QueryBuf<T> {
+    /// Convert this buffer into a [`SharedRecords`] view so its contents can
+    /// be fanned out to multiple readers with only O(1) `clone()`/`slice()`
+    /// from then on, instead of copying the records for every new reader.
+    pub fn freeze(self) -> SharedRecords<T> {
+        SharedRecords::from_records(self.0.records().to_vec())
+    }
+}
+
 #[cfg(test)]
 #[cfg(any(feature = "zerocopy", feature = "bytemuck"))]
 mod tests {
@@ -132,4 +150,30 @@ mod tests {
     fn zero_capacity_panic() {
         QueryBuf::<Log>::new(0);
     }
+
+    #[test]
+    fn take() {
+        let mut buf = QueryBuf::new(CAPACITY);
+        let records: Vec<_> = (1..=CAPACITY as u64).map(Log).collect();
+        buf.extend(&records);
+
+        let take = buf.take(10);
+        assert_eq!(take.len(), 10);
+        assert_eq!(take.records(), &records[..10]);
+    }
+
+    #[test]
+    fn freeze() {
+        let mut buf = QueryBuf::new(CAPACITY);
+        let records: Vec<_> = (1..=CAPACITY as u64).map(Log).collect();
+        buf.extend(&records);
+
+        let shared = buf.freeze();
+        assert_eq!(shared.len(), CAPACITY);
+        assert_eq!(shared.records(), &records);
+
+        // Cloning is shallow; every clone sees the same records.
+        let clone = shared.clone();
+        assert_eq!(clone.records(), shared.records());
+    }
 }
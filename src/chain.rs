@@ -0,0 +1,210 @@
+//! Definition of a zero-copy adapter chaining record segments into one logical sequence.
+
+use std::ops::Index;
+
+use crate::{QueryBuf, SeqRecord};
+
+/// A zero-copy view presenting two `&[T]` segments as one logical sequence,
+/// inspired by [`bytes::buf::Chain`].
+///
+/// The common source is a ring buffer query whose answer straddles a
+/// wraparound boundary: instead of copying both runs into a [`QueryBuf`] just
+/// to iterate or binary-search over them, wrap them in a [`Chain`] and pay for
+/// the copy only if [`Chain::to_query_buf`] is actually called.
+#[derive(Debug, Clone, Copy)]
+pub struct Chain<'a, T> {
+    first: &'a [T],
+    second: &'a [T],
+}
+
+impl<'a, T> Chain<'a, T> {
+    /// Chain two segments into one logical sequence, `first` followed by `second`.
+    ///
+    /// # Arguments
+    ///
+    /// * `first` - Leading segment.
+    /// * `second` - Trailing segment.
+    pub fn new(first: &'a [T], second: &'a [T]) -> Self {
+        Self { first, second }
+    }
+
+    /// Total number of records across both segments.
+    pub fn len(&self) -> usize {
+        self.first.len() + self.second.len()
+    }
+
+    /// Whether both segments are empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterate every record across both segments, in logical order.
+    pub fn records_iter(&self) -> impl Iterator<Item = &'a T> {
+        self.first.iter().chain(self.second.iter())
+    }
+
+    /// Sub-chain exposing at most `n` records, without copying.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - Maximum number of records the returned chain exposes.
+    pub fn take(&self, n: usize) -> Chain<'a, T> {
+        let first_n = n.min(self.first.len());
+        let second_n = (n - first_n).min(self.second.len());
+
+        Chain::new(&self.first[..first_n], &self.second[..second_n])
+    }
+}
+
+impl<'a, T: SeqRecord> Chain<'a, T> {
+    /// Sub-range of this chain whose sequence numbers fall in `start..end`.
+    ///
+    /// Because both segments are already individually sorted by [`SeqRecord::seq_no`]
+    /// and every sequence number in `first` precedes every sequence number in
+    /// `second`, binary-searching each segment independently and stitching the
+    /// cuts back together is exact.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - Inclusive lower bound on sequence number.
+    /// * `end` - Exclusive upper bound on sequence number.
+    pub fn by_seq_no(&self, start: u64, end: u64) -> Chain<'a, T> {
+        let cut = |records: &'a [T], bound: u64| {
+            records
+                .binary_search_by_key(&bound, SeqRecord::seq_no)
+                .unwrap_or_else(|index| index)
+        };
+
+        let first_start = cut(self.first, start);
+        let first_end = cut(self.first, end);
+        let second_start = cut(self.second, start);
+        let second_end = cut(self.second, end);
+
+        Chain::new(
+            &self.first[first_start..first_end],
+            &self.second[second_start..second_end],
+        )
+    }
+}
+
+impl<T: SeqRecord + Copy> Chain<'_, T> {
+    /// Copy every record across both segments into `buf`, for callers that
+    /// genuinely need one flat, owned slice.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - Buffer to copy records into; existing contents are cleared first.
+    pub fn to_query_buf(&self, buf: &mut QueryBuf<T>) {
+        buf.clear();
+
+        if !self.first.is_empty() {
+            buf.extend(self.first);
+        }
+
+        if !self.second.is_empty() {
+            buf.extend(self.second);
+        }
+    }
+}
+
+impl<T> Index<usize> for Chain<'_, T> {
+    type Output = T;
+
+    /// Record at logical position `index`, where `0` is the first record of `first`.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if `index >= self.len()`.
+    fn index(&self, index: usize) -> &T {
+        match self.first.len() {
+            len if index < len => &self.first[index],
+            len => &self.second[index - len],
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(any(feature = "zerocopy", feature = "bytemuck"))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "bytemuck")]
+    use bytemuck::{Pod, Zeroable};
+
+    #[cfg(feature = "zerocopy")]
+    use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+    #[cfg(feature = "zerocopy")]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, FromBytes, IntoBytes, Immutable, KnownLayout)]
+    struct Log(u64);
+
+    #[cfg(feature = "bytemuck")]
+    #[repr(transparent)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+    struct Log(u64);
+
+    impl SeqRecord for Log {
+        fn seq_no(&self) -> u64 {
+            self.0
+        }
+    }
+
+    fn logs(range: std::ops::RangeInclusive<u64>) -> Vec<Log> {
+        range.map(Log).collect()
+    }
+
+    #[test]
+    fn len_and_index_span_both_segments() {
+        let first = logs(1..=4);
+        let second = logs(5..=8);
+        let chain = Chain::new(&first, &second);
+
+        assert_eq!(chain.len(), 8);
+        for seq_no in 1..=8u64 {
+            assert_eq!(chain[(seq_no - 1) as usize], Log(seq_no));
+        }
+    }
+
+    #[test]
+    fn records_iter_walks_segment_boundary_transparently() {
+        let first = logs(1..=4);
+        let second = logs(5..=8);
+        let chain = Chain::new(&first, &second);
+
+        let collected: Vec<_> = chain.records_iter().copied().collect();
+        assert_eq!(collected, logs(1..=8));
+    }
+
+    #[test]
+    fn by_seq_no_cuts_across_both_segments() {
+        let first = logs(1..=4);
+        let second = logs(5..=8);
+        let chain = Chain::new(&first, &second);
+
+        let sliced = chain.by_seq_no(3, 7);
+        let collected: Vec<_> = sliced.records_iter().copied().collect();
+        assert_eq!(collected, logs(3..=6));
+    }
+
+    #[test]
+    fn take_caps_across_both_segments() {
+        let first = logs(1..=4);
+        let second = logs(5..=8);
+        let chain = Chain::new(&first, &second);
+
+        let taken = chain.take(6);
+        let collected: Vec<_> = taken.records_iter().copied().collect();
+        assert_eq!(collected, logs(1..=6));
+    }
+
+    #[test]
+    fn to_query_buf_copies_both_segments_in_order() {
+        let first = logs(1..=4);
+        let second = logs(5..=8);
+        let chain = Chain::new(&first, &second);
+
+        let mut buf = QueryBuf::new(8);
+        chain.to_query_buf(&mut buf);
+        assert_eq!(buf.records(), &logs(1..=8));
+    }
+}
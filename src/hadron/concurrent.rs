@@ -0,0 +1,357 @@
+//! A lock-free single-producer/single-consumer ring buffer transport.
+
+use crate::heap::Array;
+use crossbeam_utils::CachePadded;
+use std::sync::atomic::{AtomicI32, AtomicUsize, Ordering};
+use thiserror::Error;
+
+/// Length, in bytes, of the fixed record header (`length` + `msg_type`).
+const HEADER_LENGTH: usize = 8;
+
+/// Record bodies are aligned up to this many bytes, so every record (and any
+/// padding record) starts on an aligned boundary.
+const ALIGNMENT: usize = 8;
+
+/// Sentinel `msg_type` written into a record header to mark a run of bytes
+/// that exists only to skip to the end of the buffer, so that a real record
+/// is never physically split across the wrap-around point.
+const PADDING_MSG_TYPE_ID: i32 = -1;
+
+/// Errors that can happen publishing a message into a [`ConcurrentHadron`].
+#[derive(Debug, Error)]
+pub enum PublishError {
+    #[error("Message of {0} bytes cannot fit in a buffer of capacity {1}")]
+    MessageTooLarge(usize, usize),
+
+    #[error("Not enough free space to publish message, missing {0} bytes")]
+    InsufficientSpace(usize),
+}
+
+/// A lock-free ring buffer transport for exactly one producer thread and one
+/// consumer thread, modeled on the Aeron one-way ring buffer.
+///
+/// Unlike [`Hadron`](crate::Hadron), which is a plain memcpy ring for a single
+/// thread, `ConcurrentHadron` frames variable-length messages so a producer and
+/// a consumer can hand data across threads without taking a lock. Every message
+/// is stored as a record: a `length`/`msg_type` header followed by the message
+/// body, aligned up to [`ALIGNMENT`] bytes. The producer writes the body first
+/// and publishes the record by storing its length with release ordering; the
+/// consumer only trusts a record once it has loaded a non-zero length with
+/// acquire ordering, which is exactly the happens-before edge it needs to
+/// safely read the body written by the other thread.
+///
+/// `tail` (producer write position) and `head` (consumer read position) each
+/// live on their own cache line so the two threads never contend over a cache
+/// line neither of them other owns.
+pub struct ConcurrentHadron {
+    // Byte capacity of the buffer. Always a power of two so wrap-around can
+    // be computed with a mask instead of a modulo.
+    capacity: usize,
+    mask: usize,
+
+    // Producer write position. Only ever written by the producer.
+    tail: CachePadded<AtomicUsize>,
+
+    // Consumer read position. Only ever written by the consumer.
+    head: CachePadded<AtomicUsize>,
+
+    // Shared backing memory for framed records.
+    buffer: Array<u8>,
+}
+
+// Safety: All access to `buffer` is mediated by the atomic `length` header
+// written at the start of each record (release on publish, acquire on read),
+// which establishes a happens-before edge between the producer and consumer
+// for every byte they exchange.
+unsafe impl Sync for ConcurrentHadron {}
+
+impl ConcurrentHadron {
+    /// Create a new instance of this ring buffer.
+    ///
+    /// # Panic
+    ///
+    /// * `capacity` must be a power of two and at least [`ALIGNMENT`] bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Byte capacity of the shared buffer.
+    #[track_caller]
+    pub fn with_capacity(capacity: usize) -> Self {
+        assert!(capacity.is_power_of_two(), "Capacity must be a power of two");
+        assert!(capacity >= ALIGNMENT, "Capacity must be >= {ALIGNMENT}");
+
+        Self {
+            capacity,
+            mask: capacity - 1,
+            tail: CachePadded::new(AtomicUsize::new(0)),
+            head: CachePadded::new(AtomicUsize::new(0)),
+            // Zeroed so every unpublished slot reads back a `length` of 0;
+            // `read` relies on that to tell a real record from an unwritten
+            // or not-yet-rewound slot.
+            buffer: Array::alloc_zeroed(capacity),
+        }
+    }
+
+    /// Publish a message into the ring buffer.
+    ///
+    /// Never blocks; if there isn't enough free space right now, returns
+    /// [`PublishError`] instead of waiting for the consumer to catch up.
+    ///
+    /// # Panic
+    ///
+    /// * `msg_type` must not be the reserved padding sentinel.
+    ///
+    /// # Arguments
+    ///
+    /// * `msg_type` - Caller defined type/correlation id for the message.
+    /// * `body` - Message body to publish.
+    pub fn write(&self, msg_type: i32, body: &[u8]) -> Result<(), PublishError> {
+        assert!(
+            msg_type != PADDING_MSG_TYPE_ID,
+            "msg_type {PADDING_MSG_TYPE_ID} is reserved for padding records"
+        );
+
+        let record_len = HEADER_LENGTH + body.len();
+        let aligned_len = Self::align_up(record_len);
+        if aligned_len > self.capacity {
+            return Err(PublishError::MessageTooLarge(body.len(), self.capacity));
+        }
+
+        // Only the producer ever advances `tail`, so relaxed is sufficient.
+        let tail = self.tail.load(Ordering::Relaxed);
+
+        // Acquire to synchronize with the consumer's release store to `head`,
+        // so the free space computed below accounts for records it has
+        // already finished reading.
+        let head = self.head.load(Ordering::Acquire);
+        let available = self.capacity - (tail - head);
+
+        let index = tail & self.mask;
+        let to_end = self.capacity - index;
+
+        // If the record would straddle the end of the buffer, reserve a
+        // padding record to skip to the end and wrap the real record to 0.
+        let needs_padding = aligned_len > to_end;
+        let required = if needs_padding {
+            to_end + aligned_len
+        } else {
+            aligned_len
+        };
+
+        if required > available {
+            return Err(PublishError::InsufficientSpace(required - available));
+        }
+
+        let write_index = if needs_padding {
+            // Safety: [index, index + to_end) belongs exclusively to this
+            // write; `tail` has not yet been advanced past it.
+            unsafe { self.write_padding(index, to_end) };
+            0
+        } else {
+            index
+        };
+
+        // Safety: [write_index, write_index + record_len) belongs exclusively
+        // to this write.
+        unsafe { self.write_record(write_index, msg_type, body, record_len) };
+
+        // Advance tail last, so a consumer racing ahead of us can never see a
+        // tail that outpaces the records actually published.
+        self.tail.store(tail + required, Ordering::Release);
+
+        Ok(())
+    }
+
+    /// Read messages published into the ring buffer, invoking `handler` once
+    /// for each, in publish order.
+    ///
+    /// Stops once it has read `message_limit` messages or caught up to the
+    /// producer, whichever happens first.
+    ///
+    /// # Arguments
+    ///
+    /// * `handler` - Called with `(msg_type, body)` for every message read.
+    /// * `message_limit` - Maximum number of messages to read in this call.
+    pub fn read(&self, mut handler: impl FnMut(i32, &[u8]), message_limit: usize) -> usize {
+        // Only the consumer ever advances `head`, so relaxed is sufficient.
+        let head = self.head.load(Ordering::Relaxed);
+
+        let mut consumed = 0;
+        let mut messages_read = 0;
+        while messages_read < message_limit {
+            let index = (head + consumed) & self.mask;
+
+            // Acquire to synchronize with the producer's release store of the
+            // length header; a non-zero length guarantees the msg_type and
+            // body bytes read below are fully visible.
+            let length = unsafe { self.read_length(index) };
+            if length == 0 {
+                break; // Not yet published.
+            }
+
+            let aligned_len = Self::align_up(length as usize);
+            let msg_type = unsafe { self.read_msg_type(index) };
+            if msg_type != PADDING_MSG_TYPE_ID {
+                let body = unsafe { self.read_body(index, length as usize - HEADER_LENGTH) };
+                handler(msg_type, body);
+                messages_read += 1;
+            }
+
+            // Safety: the record at `index` has now been fully handled (or
+            // was padding), so its bytes can be zeroed and reclaimed.
+            unsafe { self.zero(index, aligned_len) };
+            consumed += aligned_len;
+        }
+
+        if consumed > 0 {
+            // Release so the producer's next acquire load of `head` observes
+            // the space we just freed.
+            self.head.store(head + consumed, Ordering::Release);
+        }
+
+        messages_read
+    }
+
+    /// Byte capacity of this buffer.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Round `len` up to the next multiple of [`ALIGNMENT`].
+    fn align_up(len: usize) -> usize {
+        (len + ALIGNMENT - 1) & !(ALIGNMENT - 1)
+    }
+
+    unsafe fn write_record(&self, index: usize, msg_type: i32, body: &[u8], record_len: usize) {
+        unsafe {
+            let msg_type_ptr = self.buffer.as_ptr().add(index + 4) as *mut i32;
+            msg_type_ptr.write_unaligned(msg_type);
+
+            let body_ptr = self.buffer.as_ptr().add(index + HEADER_LENGTH);
+            std::ptr::copy_nonoverlapping(body.as_ptr(), body_ptr, body.len());
+
+            // Publish last, with release ordering, so the consumer never
+            // observes a non-zero length before the msg_type/body writes above.
+            let length_ptr = self.buffer.as_ptr().add(index) as *mut i32;
+            AtomicI32::from_ptr(length_ptr).store(record_len as i32, Ordering::Release);
+        }
+    }
+
+    unsafe fn write_padding(&self, index: usize, len: usize) {
+        unsafe {
+            let msg_type_ptr = self.buffer.as_ptr().add(index + 4) as *mut i32;
+            msg_type_ptr.write_unaligned(PADDING_MSG_TYPE_ID);
+
+            let length_ptr = self.buffer.as_ptr().add(index) as *mut i32;
+            AtomicI32::from_ptr(length_ptr).store(len as i32, Ordering::Release);
+        }
+    }
+
+    unsafe fn read_length(&self, index: usize) -> i32 {
+        unsafe {
+            let length_ptr = self.buffer.as_ptr().add(index) as *mut i32;
+            AtomicI32::from_ptr(length_ptr).load(Ordering::Acquire)
+        }
+    }
+
+    unsafe fn read_msg_type(&self, index: usize) -> i32 {
+        unsafe {
+            let msg_type_ptr = self.buffer.as_ptr().add(index + 4) as *const i32;
+            msg_type_ptr.read_unaligned()
+        }
+    }
+
+    unsafe fn read_body(&self, index: usize, len: usize) -> &[u8] {
+        unsafe {
+            let body_ptr = self.buffer.as_ptr().add(index + HEADER_LENGTH);
+            std::slice::from_raw_parts(body_ptr, len)
+        }
+    }
+
+    unsafe fn zero(&self, index: usize, len: usize) {
+        unsafe {
+            let ptr = self.buffer.as_ptr().add(index);
+            ptr.write_bytes(0, len);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bolero::{TypeGenerator, check, generator};
+    use std::{sync::Arc, thread};
+
+    #[derive(Debug, Clone, TypeGenerator)]
+    struct Message {
+        msg_type: i32,
+        #[generator(generator::produce::<Vec<u8>>().with().len(0..=64))]
+        body: Vec<u8>,
+    }
+
+    #[test]
+    fn single_threaded_round_trip() {
+        check!()
+            .with_generator(generator::produce::<Vec<Message>>())
+            .for_each(|messages| {
+                let hadron = ConcurrentHadron::with_capacity(1024);
+                let mut expected = Vec::new();
+                let mut received = Vec::new();
+
+                for message in messages {
+                    // Reserved sentinel, not a valid user msg_type.
+                    if message.msg_type == PADDING_MSG_TYPE_ID {
+                        continue;
+                    }
+
+                    match hadron.write(message.msg_type, &message.body) {
+                        Ok(()) => expected.push((message.msg_type, message.body.clone())),
+                        Err(_) => {
+                            // Buffer full; drain once, then retry the write.
+                            hadron.read(
+                                |msg_type, body| received.push((msg_type, body.to_vec())),
+                                usize::MAX,
+                            );
+
+                            if hadron.write(message.msg_type, &message.body).is_ok() {
+                                expected.push((message.msg_type, message.body));
+                            }
+                        }
+                    }
+                }
+
+                hadron.read(
+                    |msg_type, body| received.push((msg_type, body.to_vec())),
+                    usize::MAX,
+                );
+
+                assert_eq!(expected, received);
+            });
+    }
+
+    #[test]
+    fn concurrent_producer_consumer() {
+        const MESSAGES: i32 = 10_000;
+        let hadron = Arc::new(ConcurrentHadron::with_capacity(4096));
+
+        let producer = {
+            let hadron = hadron.clone();
+            thread::spawn(move || {
+                let mut msg_type = 0;
+                while msg_type < MESSAGES {
+                    if hadron.write(msg_type, &msg_type.to_le_bytes()).is_ok() {
+                        msg_type += 1;
+                    }
+                }
+            })
+        };
+
+        let mut received = Vec::with_capacity(MESSAGES as usize);
+        while received.len() < MESSAGES as usize {
+            hadron.read(|msg_type, _body| received.push(msg_type), usize::MAX);
+        }
+
+        producer.join().expect("Producer thread should not panic");
+        assert_eq!(received, (0..MESSAGES).collect::<Vec<_>>());
+    }
+}
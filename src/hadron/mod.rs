@@ -0,0 +1,755 @@
+//! Definition of a ring buffer.
+
+mod concurrent;
+
+use crate::heap::Array;
+use std::{cmp::min, fmt::Debug, mem::MaybeUninit};
+
+pub use concurrent::{ConcurrentHadron, PublishError};
+
+/// Hadron is a fixed size ring buffer.
+///
+/// It is designed for high performance use cases and makes trade-offs to achieve it.
+/// Bulk append and copy is guaranteed to be exactly 2 memcpy operations. Additionally
+/// provides reference to all the items held in constant time.
+pub struct Hadron<T> {
+    // Logical write position. In arbitrary-capacity mode this is always a
+    // physical index, i.e. < cap, and wraps back to 0 when it reaches cap. In
+    // power-of-two mode this is a monotonically increasing counter; the
+    // physical index is derived from it on demand via `mask`.
+    next: usize,
+
+    // Physical index of the oldest record currently held. Unlike `next`,
+    // always a physical index regardless of mode, since every update to it
+    // either wraps modulo capacity or resets it to 0.
+    start: usize,
+
+    // Number of records currently held in the ring buffer.
+    length: usize,
+
+    // Number of the oldest `length` records that a consumer has already
+    // dequeued, i.e. the logical read cursor. Always <= length.
+    read_at: usize,
+
+    // A pre-allocated memory for ring buffer records.
+    memory: Array<T>,
+
+    // `Some(capacity - 1)` when capacity is a power of two, letting physical
+    // indices be derived with `next & mask` instead of a modulo. `None` for
+    // the arbitrary-capacity path, where `next` is already a physical index.
+    mask: Option<usize>,
+
+    // Capacity callers are steering towards, distinct from `memory.len()`
+    // (the actual allocated capacity). Purely informational: adjusting it
+    // doesn't reallocate on its own, it's there for a caller-driven policy
+    // that calls `resize` once observed occupancy crosses some threshold.
+    target_capacity: usize,
+}
+
+impl<T> Hadron<T> {
+    /// Create a new instance of this ring buffer.
+    ///
+    /// All required memory is allocated during initialization. It is
+    /// guaranteed that no allocations happen after initialization, other than
+    /// in [`Hadron::resize`].
+    ///
+    /// # Panic
+    ///
+    /// * Ring buffer must have at least one item.
+    /// * Number of items in bytes should be <= isize::MAX.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum number of items this ring buffer can hold.
+    #[track_caller]
+    pub fn with_capacity(capacity: usize) -> Self {
+        assert!(capacity > 0, "Capacity must be > 0");
+
+        Self {
+            next: 0,
+            start: 0,
+            length: 0,
+            read_at: 0,
+            memory: Array::alloc(capacity),
+            mask: None,
+            target_capacity: capacity,
+        }
+    }
+
+    /// Create a new instance of this ring buffer whose actual capacity is
+    /// rounded up to the next power of two.
+    ///
+    /// This trades an exact capacity bound for a faster append hot path: the
+    /// physical write index is derived with a bitmask instead of the
+    /// subtraction-based wrap math [`Hadron::with_capacity`] uses. Prefer this
+    /// constructor when callers don't need an exact capacity and can instead
+    /// work off [`Hadron::capacity`].
+    ///
+    /// # Panic
+    ///
+    /// * Ring buffer must have at least one item.
+    /// * Number of items in bytes should be <= isize::MAX.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Minimum number of items this ring buffer can hold.
+    #[track_caller]
+    pub fn with_capacity_pow2(capacity: usize) -> Self {
+        assert!(capacity > 0, "Capacity must be > 0");
+
+        let requested = capacity;
+        let capacity = capacity.next_power_of_two();
+        Self {
+            next: 0,
+            start: 0,
+            length: 0,
+            read_at: 0,
+            memory: Array::alloc(capacity),
+            mask: Some(capacity - 1),
+            target_capacity: requested,
+        }
+    }
+
+    /// Maximum number of items this ring buffer can hold right now.
+    ///
+    /// For a ring buffer created via [`Hadron::with_capacity_pow2`], this may
+    /// be larger than the capacity originally requested. Distinct from
+    /// [`Hadron::target_capacity`], which reflects what the ring buffer is
+    /// being steered towards rather than what is currently allocated.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.memory.len()
+    }
+
+    /// Capacity callers are currently steering this ring buffer towards, via
+    /// an adaptive sizing policy built on top of [`Hadron::resize`].
+    ///
+    /// Defaults to the capacity requested at construction and is otherwise
+    /// just a value set by [`Hadron::set_target_capacity`]; it has no effect
+    /// on its own; it does not trigger a resize.
+    #[inline]
+    pub fn target_capacity(&self) -> usize {
+        self.target_capacity
+    }
+
+    /// Record the capacity a caller wants this ring buffer to grow or shrink
+    /// towards, without resizing anything yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Capacity to steer towards on a future [`Hadron::resize`].
+    #[inline]
+    pub fn set_target_capacity(&mut self, target: usize) {
+        self.target_capacity = target;
+    }
+
+    /// Derive the physical index of `next` into `memory`.
+    #[inline]
+    fn physical(&self, next: usize) -> usize {
+        match self.mask {
+            Some(mask) => next & mask,
+            None => next,
+        }
+    }
+
+    /// Get a reference to items currently stored in the ring buffer.
+    ///
+    /// Since the ring buffer can wrap around, items in the ring buffer are stored
+    /// in two non-overlapping discrete chunks of items. Tail is empty unless the
+    /// items held run past the end of memory, which after [`Hadron::advance`] can
+    /// happen before the ring buffer is full.
+    #[inline]
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let capacity = self.memory.len();
+        if self.start + self.length <= capacity {
+            // Doesn't wrap around the end of the ring buffer.
+            let head = self.memory.as_slice(self.start, self.length);
+
+            (head, Default::default())
+        } else {
+            // Head of the ring buffer.
+            let head = self.memory.as_slice(self.start, capacity - self.start);
+
+            // Tail of the ring buffer.
+            let tail = self.memory.as_slice(0, self.length - head.len());
+
+            // Return both halves of the ring buffer.
+            (head, tail)
+        }
+    }
+
+    /// An iterator to iterate through all the items currently in ring buffer.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let (head, tail) = self.as_slices();
+        head.iter().chain(tail.iter())
+    }
+
+    /// Number of items held that [`Hadron::dequeue_into`] has not consumed yet
+    /// ([`Hadron::peek`] does not consume), i.e. how far the consumer is behind the producer.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.length - self.read_at
+    }
+
+    /// Whether there are no unread items in the ring buffer.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Free slots a consumer-aware writer can fill without clobbering items
+    /// that have not been read yet.
+    ///
+    /// Unlike [`Hadron::copy_from_slice`], which always has `capacity` slots
+    /// available because it evicts unread data when necessary, this reflects
+    /// only the slots that are either empty or already read.
+    #[inline]
+    pub fn window(&self) -> usize {
+        self.memory.len() - self.len()
+    }
+
+    /// Get a reference to unread items without advancing the read cursor.
+    ///
+    /// `offset` skips that many unread items from the front, and at most
+    /// `max` items are returned. Like [`Hadron::as_slices`], the result is
+    /// split into two chunks when it straddles the end of the ring buffer.
+    #[inline]
+    pub fn peek(&self, offset: usize, max: usize) -> (&[T], &[T]) {
+        let unread = self.len();
+        let start = offset.min(unread);
+        let len = max.min(unread - start);
+        self.slice_at(self.read_at + start, len)
+    }
+
+    /// Get a reference to items at an arbitrary logical offset from the
+    /// oldest item currently held, without consuming anything.
+    ///
+    /// Unlike [`Hadron::peek`], `offset` is relative to the oldest item
+    /// rather than the read cursor, and unlike [`Hadron::as_slices`] a window
+    /// can start anywhere instead of always covering everything held. This
+    /// lets a sender keep unacknowledged records around and re-read a window
+    /// at a given offset to resend it.
+    #[inline]
+    pub fn allocated(&self, offset: usize, max: usize) -> (&[T], &[T]) {
+        let start = offset.min(self.length);
+        let len = max.min(self.length - start);
+        self.slice_at(start, len)
+    }
+
+    /// Drop the oldest `count` items once a consumer no longer needs them
+    /// (e.g. they've been acknowledged), freeing their slots for new writes
+    /// without waiting for a future write to wrap around and evict them.
+    ///
+    /// Integrates with the read cursor: dropped items that hadn't been read
+    /// yet are treated as read, since they can no longer be peeked at or
+    /// dequeued.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - Number of oldest items to drop.
+    #[inline]
+    pub fn advance(&mut self, count: usize) {
+        let count = count.min(self.length);
+
+        self.start = (self.start + count) % self.memory.len();
+        self.length -= count;
+        self.read_at = self.read_at.saturating_sub(count);
+    }
+
+    /// Discard all items currently held in the ring buffer, read or not.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.next = 0;
+        self.start = 0;
+        self.length = 0;
+        self.read_at = 0;
+    }
+
+    /// Get a reference to `len` items starting at logical position `start`,
+    /// where logical position 0 is the oldest item currently held.
+    #[inline]
+    fn slice_at(&self, start: usize, len: usize) -> (&[T], &[T]) {
+        let (head, tail) = self.as_slices();
+        if start >= head.len() {
+            let tail_start = start - head.len();
+            let tail = &tail[tail_start..];
+            (&tail[..len], Default::default())
+        } else if head.len() - start >= len {
+            (&head[start..start + len], Default::default())
+        } else {
+            let head = &head[start..];
+            let tail_len = len - head.len();
+            (head, &tail[..tail_len])
+        }
+    }
+
+    /// Claim up to two contiguous, possibly-uninitialized regions at the
+    /// write position, covering `count` slots.
+    ///
+    /// Lets a caller write records directly into ring buffer memory (e.g.
+    /// deserializing off the network or running a codec in place) instead of
+    /// building a slice just to have [`Hadron::copy_from_slice`] memcpy it in.
+    /// The claimed slots are not live until [`Hadron::commit`] is called; the
+    /// caller is responsible for initializing everything it claims before
+    /// committing it.
+    ///
+    /// # Panic
+    ///
+    /// * `count` must be <= [`Hadron::capacity`].
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - Number of slots to claim.
+    #[track_caller]
+    #[inline]
+    pub fn claim(&mut self, count: usize) -> (&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) {
+        let capacity = self.memory.len();
+        assert!(count <= capacity, "Claimed count {count} exceeds capacity {capacity}");
+
+        let next = self.physical(self.next);
+        let remaining = capacity - next;
+        let head_len = remaining.min(count);
+        let tail_len = count - head_len;
+
+        self.memory.as_uninit_mut_slices(next, head_len, tail_len)
+    }
+
+    /// Claim a single slot, for producers that write one record at a time.
+    #[inline]
+    pub fn claim_one(&mut self) -> Option<&mut MaybeUninit<T>> {
+        let (head, tail) = self.claim(1);
+        head.first_mut().or_else(|| tail.first_mut())
+    }
+
+    /// Make the most recently [`Hadron::claim`]ed `count` slots live, exactly
+    /// as [`Hadron::copy_from_slice`] would after copying `count` items in.
+    ///
+    /// # Safety
+    ///
+    /// The caller must have fully initialized the `count` slots returned by
+    /// the matching `claim` call before committing them.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - Number of claimed slots to commit.
+    #[inline]
+    pub unsafe fn commit(&mut self, count: usize) {
+        self.advance_write(count);
+    }
+
+    /// Advance `next`/`start`/`length`/`read_at` as if `count` items had just
+    /// been written, without touching the underlying memory.
+    #[inline]
+    fn advance_write(&mut self, count: usize) {
+        let capacity = self.memory.len();
+
+        // Items evicted from the front to make room count against both the
+        // read cursor and the oldest-item pointer: already-read items just
+        // disappear, but evicting unread items forgets that they were ever
+        // marked read, since the slots they occupied are about to hold brand
+        // new, unread data.
+        let evicted = (self.length + count).saturating_sub(capacity).min(self.length);
+        self.start = (self.start + evicted) % capacity;
+        self.read_at = self.read_at.saturating_sub(evicted);
+
+        let next = self.physical(self.next);
+        let remaining = capacity - next;
+        if remaining > count {
+            // Cannot wrap around since remaining > count.
+            self.next += count;
+
+            // To handle the case where ring buffer hasn't filled up yet.
+            self.length = min(self.length + count, capacity);
+        } else {
+            let tail_count = count - remaining;
+
+            // In power-of-two mode `next` keeps counting up rather than
+            // resetting, and the physical index is derived with the mask on
+            // the next call.
+            self.next = match self.mask {
+                Some(_) => self.next + count,
+                None => tail_count,
+            };
+
+            // Still handle the not-yet-full case here: `advance` can move `start`
+            // forward before the buffer has ever filled up, so a write that wraps
+            // physically doesn't necessarily mean the buffer is logically full.
+            self.length = min(self.length + count, capacity);
+        }
+    }
+}
+
+impl<T: Copy + Debug> Hadron<T> {
+    /// Append a slice of items into this ring buffer.
+    ///
+    /// If newly appended records exceeds the capacity of this ring buffer,
+    /// space is reclaimed by evicting old records from the ring buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - Items to append into this ring buffer.
+    #[inline]
+    pub fn copy_from_slice(&mut self, items: &[T]) {
+        // Maximum items memory can accommodate.
+        let capacity = self.memory.len();
+
+        // Index of items from where writes can begin,
+        let src = items.as_ptr();
+        let src_start = items.len().saturating_sub(capacity);
+        let src_count = items.len() - src_start;
+
+        // Physical index of the next write, and items that can be written
+        // till end of the ring buffer.
+        let next = self.physical(self.next);
+        let remaining = capacity - next;
+
+        // If remaining is >= than number of items to write,
+        // all of it can be written in one shot
+        if remaining > src_count {
+            // Everything can be copied in one shot.
+            self.memory.memcpy(next, src, src_start, src_count);
+        } else {
+            // First write out items till the end of the ring buffer.
+            self.memory.memcpy(next, src, src_start, remaining);
+
+            // Then write out the rest.
+            let tail_count = src_count - remaining;
+            let tail_start = src_start + remaining;
+            self.memory.memcpy(0, src, tail_start, tail_count);
+        }
+
+        self.advance_write(src_count);
+    }
+
+    /// Reallocate this ring buffer's actual capacity to `new_capacity`,
+    /// preserving the logical order of items currently held.
+    ///
+    /// Performs a linearizing reallocation: the two slices returned by
+    /// [`Hadron::as_slices`] are copied contiguously into fresh memory and
+    /// `next`/`length` are reset accordingly, same as a single guaranteed
+    /// 2-memcpy `copy_from_slice` would. If `new_capacity` is smaller than
+    /// the number of items currently held, the oldest ones are dropped to
+    /// make room, exactly as an append that exceeds capacity would.
+    ///
+    /// Does not touch [`Hadron::target_capacity`]; callers driving an
+    /// adaptive sizing policy are expected to keep the two in sync
+    /// themselves.
+    ///
+    /// # Panic
+    ///
+    /// * `new_capacity` must be > 0.
+    /// * Number of items in bytes should be <= isize::MAX.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_capacity` - Capacity to reallocate actual storage to. Rounded
+    ///   up to the next power of two for a ring buffer created via
+    ///   [`Hadron::with_capacity_pow2`].
+    #[track_caller]
+    pub fn resize(&mut self, new_capacity: usize) {
+        assert!(new_capacity > 0, "Capacity must be > 0");
+
+        let new_capacity = match self.mask {
+            Some(_) => new_capacity.next_power_of_two(),
+            None => new_capacity,
+        };
+
+        // Drop the oldest items if they no longer fit, same as `copy_from_slice`.
+        let keep = self.length.min(new_capacity);
+        let drop_count = self.length - keep;
+        let (head, tail) = self.as_slices();
+        let (head, tail) = if drop_count >= head.len() {
+            (&head[..0], &tail[drop_count - head.len()..])
+        } else {
+            (&head[drop_count..], tail)
+        };
+
+        let mut memory = Array::alloc(new_capacity);
+        memory.memcpy(0, head.as_ptr(), 0, head.len());
+        memory.memcpy(head.len(), tail.as_ptr(), 0, tail.len());
+
+        self.read_at = self.read_at.saturating_sub(drop_count);
+        self.memory = memory;
+        self.start = 0;
+        self.next = keep % new_capacity;
+        self.length = keep;
+        self.mask = self.mask.map(|_| new_capacity - 1);
+    }
+
+    /// Append as many `items` as fit without clobbering unread data, and
+    /// return the unwritten tail.
+    ///
+    /// Unlike [`Hadron::copy_from_slice`], this never evicts items a consumer
+    /// has not yet read via [`Hadron::peek`]/[`Hadron::dequeue_into`]: if
+    /// [`Hadron::window`] is smaller than `items`, only a prefix is written
+    /// and the rest is handed back to the caller, mirroring the rejection
+    /// contract of `Segment::extend_from_slice`.
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - Items to append into this ring buffer.
+    #[inline]
+    pub fn try_copy_from_slice<'a>(&mut self, items: &'a [T]) -> &'a [T] {
+        let accepted = self.window().min(items.len());
+        let (to_append, to_reject) = items.split_at(accepted);
+
+        if !to_append.is_empty() {
+            self.copy_from_slice(to_append);
+        }
+
+        to_reject
+    }
+
+    /// Copy all unread items into `buf` and advance the read cursor past
+    /// them.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - Destination to append unread items into.
+    #[inline]
+    pub fn dequeue_into(&mut self, buf: &mut Vec<T>) {
+        let (head, tail) = self.peek(0, self.len());
+        buf.extend_from_slice(head);
+        buf.extend_from_slice(tail);
+
+        self.read_at = self.length;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Oracle;
+    use bolero::{TypeGenerator, check, generator};
+    use std::collections::VecDeque;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, TypeGenerator)]
+    struct Log(u64);
+
+    #[test]
+    fn state_machine() {
+        check!()
+            .with_generator((
+                generator::produce::<usize>().with().bounds(1..=1024),
+                generator::produce::<Vec<Vec<Log>>>(),
+            ))
+            .for_each(|(capacity, operations)| {
+                // Ring buffers for equivalence testing.
+                let mut hadron = Hadron::with_capacity(*capacity);
+                let mut oracle = Oracle::with_capacity(*capacity);
+
+                // Process the batch of items.
+                for items in operations {
+                    // Copy the batch of items into the ring buffer.
+                    hadron.copy_from_slice(items);
+                    oracle.copy_from_slice(items);
+
+                    // Make sure items are the same between the ring buffers.
+                    let hadron_items: Vec<_> = hadron.iter().collect();
+                    let oracle_items: Vec<_> = oracle.iter().collect();
+                    assert_eq!(hadron_items, oracle_items);
+                }
+            });
+    }
+
+    #[test]
+    fn state_machine_pow2() {
+        check!()
+            .with_generator((
+                generator::produce::<usize>().with().bounds(1..=1024),
+                generator::produce::<Vec<Vec<Log>>>(),
+            ))
+            .for_each(|(capacity, operations)| {
+                // Ring buffers for equivalence testing. Oracle is sized off the
+                // rounded-up capacity, since `with_capacity_pow2` may allocate
+                // more than requested.
+                let mut hadron = Hadron::with_capacity_pow2(*capacity);
+                let mut oracle = Oracle::with_capacity(hadron.capacity());
+
+                // Process the batch of items.
+                for items in operations {
+                    // Copy the batch of items into the ring buffer.
+                    hadron.copy_from_slice(items);
+                    oracle.copy_from_slice(items);
+
+                    // Make sure items are the same between the ring buffers.
+                    let hadron_items: Vec<_> = hadron.iter().collect();
+                    let oracle_items: Vec<_> = oracle.iter().collect();
+                    assert_eq!(hadron_items, oracle_items);
+                }
+            });
+    }
+
+    #[derive(Debug, Clone, TypeGenerator)]
+    enum ReadOp {
+        Append(Vec<Log>),
+        Dequeue,
+    }
+
+    #[test]
+    fn read_cursor_never_loses_unread_items() {
+        check!()
+            .with_generator((
+                generator::produce::<usize>().with().bounds(1..=1024),
+                generator::produce::<Vec<ReadOp>>(),
+            ))
+            .for_each(|(capacity, operations)| {
+                let mut hadron = Hadron::with_capacity(*capacity);
+
+                for op in operations {
+                    match op {
+                        ReadOp::Append(items) => {
+                            // Snapshot what the consumer hasn't read yet before
+                            // appending, so we can make sure the non-destructive
+                            // write never clobbers it.
+                            let (head, tail) = hadron.peek(0, hadron.len());
+                            let unread: Vec<_> = head.iter().chain(tail).copied().collect();
+
+                            let window = hadron.window();
+                            let rejected = hadron.try_copy_from_slice(items).len();
+                            assert_eq!(rejected, items.len().saturating_sub(window));
+
+                            let (head, tail) = hadron.peek(0, unread.len());
+                            let still_unread: Vec<_> = head.iter().chain(tail).copied().collect();
+                            assert_eq!(unread, still_unread);
+                        }
+                        ReadOp::Dequeue => {
+                            let (head, tail) = hadron.peek(0, hadron.len());
+                            let expected: Vec<_> = head.iter().chain(tail).copied().collect();
+
+                            let mut dequeued = Vec::new();
+                            hadron.dequeue_into(&mut dequeued);
+
+                            assert_eq!(expected, dequeued);
+                            assert_eq!(hadron.len(), 0);
+                        }
+                    }
+
+                    // The ring buffer never reports more unread items, or more
+                    // free slots, than it has capacity for.
+                    assert_eq!(hadron.len() + hadron.window(), hadron.capacity());
+                }
+            });
+    }
+
+    #[test]
+    fn claim_commit_matches_copy_from_slice() {
+        check!()
+            .with_generator((
+                generator::produce::<usize>().with().bounds(1..=1024),
+                generator::produce::<Vec<Vec<Log>>>(),
+            ))
+            .for_each(|(capacity, operations)| {
+                // Twin ring buffers: one written with `copy_from_slice`, the
+                // other with `claim`/`commit`, to verify they stay equivalent.
+                let mut copied = Hadron::with_capacity(*capacity);
+                let mut claimed = Hadron::with_capacity(*capacity);
+
+                for items in operations {
+                    copied.copy_from_slice(items);
+
+                    // Claiming more than capacity would panic, matching the
+                    // fact that `copy_from_slice` only ever keeps the last
+                    // `capacity` items too.
+                    let count = items.len().min(claimed.capacity());
+                    let to_claim = &items[items.len() - count..];
+                    let (head, tail) = claimed.claim(count);
+                    for (slot, item) in head.iter_mut().chain(tail).zip(to_claim) {
+                        slot.write(*item);
+                    }
+
+                    // Safety: every claimed slot was just initialized above.
+                    unsafe { claimed.commit(count) };
+
+                    let copied_items: Vec<_> = copied.iter().collect();
+                    let claimed_items: Vec<_> = claimed.iter().collect();
+                    assert_eq!(copied_items, claimed_items);
+                }
+            });
+    }
+
+    #[derive(Debug, Clone, TypeGenerator)]
+    enum AckOp {
+        Append(Vec<Log>),
+        Ack(usize),
+    }
+
+    #[test]
+    fn advance_drops_oldest_items_in_order() {
+        check!()
+            .with_generator((
+                generator::produce::<usize>().with().bounds(1..=1024),
+                generator::produce::<Vec<AckOp>>(),
+            ))
+            .for_each(|(capacity, operations)| {
+                let mut hadron = Hadron::with_capacity(*capacity);
+
+                // Mirrors `Hadron`'s own eviction semantics, so `Ack` is the
+                // only operation below that actually exercises new behavior.
+                let mut model: VecDeque<Log> = Default::default();
+
+                for op in operations {
+                    match op {
+                        AckOp::Append(items) => {
+                            hadron.copy_from_slice(items);
+
+                            // Mirror the same front-eviction `copy_from_slice` does.
+                            let keep = items.len().min(hadron.capacity());
+                            model.extend(items[items.len() - keep..].iter().copied());
+                            while model.len() > hadron.capacity() {
+                                model.pop_front();
+                            }
+                        }
+                        AckOp::Ack(count) => {
+                            let count = (*count).min(model.len());
+                            hadron.advance(count);
+                            model.drain(..count);
+                        }
+                    }
+
+                    let hadron_items: Vec<_> = hadron.iter().collect();
+                    let model_items: Vec<_> = model.iter().collect();
+                    assert_eq!(hadron_items, model_items);
+
+                    // `allocated` from offset 0 covering everything held must
+                    // agree with `as_slices`/`iter`.
+                    let (head, tail) = hadron.allocated(0, hadron.capacity());
+                    let allocated_items: Vec<_> = head.iter().chain(tail).collect();
+                    assert_eq!(allocated_items, model_items);
+                }
+            });
+    }
+
+    #[test]
+    fn resize_preserves_order_and_keeps_newest_items() {
+        check!()
+            .with_generator((
+                generator::produce::<usize>().with().bounds(1..=1024),
+                generator::produce::<Vec<Vec<Log>>>(),
+                generator::produce::<usize>().with().bounds(1..=1024),
+            ))
+            .for_each(|(capacity, operations, new_capacity)| {
+                let mut hadron = Hadron::with_capacity(*capacity);
+                for items in operations {
+                    hadron.copy_from_slice(items);
+                }
+
+                let before: Vec<_> = hadron.iter().copied().collect();
+                hadron.resize(*new_capacity);
+
+                assert_eq!(hadron.capacity(), *new_capacity);
+                assert_eq!(hadron.target_capacity(), *capacity);
+
+                // Only the newest items that fit survive the resize.
+                let kept = before.len().min(*new_capacity);
+                let expected = &before[before.len() - kept..];
+                let after: Vec<_> = hadron.iter().copied().collect();
+                assert_eq!(after, expected);
+
+                // Both the read cursor and append invariants still hold
+                // against the new, resized capacity.
+                assert_eq!(hadron.len() + hadron.window(), hadron.capacity());
+            });
+    }
+}
@@ -1,7 +1,8 @@
 use std::{
-    alloc::{Layout, alloc, dealloc, handle_alloc_error},
+    alloc::{Layout, alloc, alloc_zeroed, dealloc, handle_alloc_error},
+    mem::MaybeUninit,
     ptr::copy_nonoverlapping,
-    slice::from_raw_parts,
+    slice::{from_raw_parts, from_raw_parts_mut},
 };
 
 unsafe impl<T> Sync for Array<T> {}
@@ -32,10 +33,43 @@ impl<T> Array<T> {
         }
     }
 
+    /// Like [`Self::alloc`], but the memory is zero-initialized.
+    ///
+    /// Use this when readers may observe a slot before a writer has ever
+    /// published into it, and zero is the sentinel that means "nothing here
+    /// yet" (e.g. a `length` header read racily without its own happens-before
+    /// edge).
+    pub(crate) fn alloc_zeroed(len: usize) -> Self {
+        let Ok(layout) = Layout::array::<T>(len) else {
+            panic!("Invalid array allocation of size {len}");
+        };
+
+        // Safety: Made sure layout is valid above.
+        let ptr = unsafe { alloc_zeroed(layout) };
+        if ptr.is_null() {
+            handle_alloc_error(layout);
+        }
+
+        Self {
+            len,
+            layout,
+            ptr: ptr as *mut T,
+        }
+    }
+
     pub(crate) fn len(&self) -> usize {
         self.len
     }
 
+    /// Raw pointer to the start of this allocation.
+    ///
+    /// Callers that share this pointer across threads are responsible for
+    /// synchronizing access; this type on its own only guarantees the memory
+    /// is allocated for `len` items.
+    pub(crate) fn as_ptr(&self) -> *mut T {
+        self.ptr
+    }
+
     pub(crate) fn memcpy(
         &mut self,
         dst_index: usize,
@@ -58,6 +92,28 @@ impl<T> Array<T> {
             from_raw_parts(ptr, len)
         }
     }
+
+    /// A pair of non-overlapping mutable, possibly-uninitialized views into
+    /// this allocation: `head_len` items starting at `index`, and `tail_len`
+    /// items starting at 0.
+    pub(crate) fn as_uninit_mut_slices(
+        &mut self,
+        index: usize,
+        head_len: usize,
+        tail_len: usize,
+    ) -> (&mut [MaybeUninit<T>], &mut [MaybeUninit<T>]) {
+        // Safety: Safety must be upheld by the caller, who must ensure
+        // `[index, index + head_len)` and `[0, tail_len)` don't overlap.
+        unsafe {
+            let head_ptr = self.ptr.add(index) as *mut MaybeUninit<T>;
+            let head = from_raw_parts_mut(head_ptr, head_len);
+
+            let tail_ptr = self.ptr as *mut MaybeUninit<T>;
+            let tail = from_raw_parts_mut(tail_ptr, tail_len);
+
+            (head, tail)
+        }
+    }
 }
 
 impl<T> Drop for Array<T> {
@@ -0,0 +1,254 @@
+//! [`std::io`] interop for persisting [`QueryBuf`] contents without coalescing.
+
+use std::io::{self, IoSlice, Read, Write};
+
+use crate::{QueryBuf, Record, SeqRecord};
+
+impl<T: SeqRecord + Record> QueryBuf<T> {
+    /// Vectored, zero-copy view over this buffer's records.
+    ///
+    /// Yields a single [`IoSlice`] today since [`QueryBuf`] is backed by one
+    /// contiguous allocation; types that wrap records across multiple segments
+    /// (e.g. a ring buffer view straddling its backing store's wraparound)
+    /// can yield more, letting a caller persist everything with one `writev`
+    /// instead of copying into an intermediate buffer first.
+    pub fn io_slices(&self) -> impl Iterator<Item = IoSlice<'_>> {
+        std::iter::once(IoSlice::new(Record::to_bytes_slice(self.records())))
+    }
+
+    /// Adapter that drains this buffer's records as a flat byte stream, for
+    /// interop with `std::io` consumers (file/socket/compression layers) that
+    /// don't know about record framing.
+    pub fn reader(&self) -> Reader<'_, T> {
+        Reader {
+            buf: self,
+            offset: 0,
+        }
+    }
+
+    /// Adapter that fills this buffer from any [`Read`], committing bytes as
+    /// whole records as soon as enough have arrived.
+    pub fn writer(&mut self) -> Writer<'_, T> {
+        Writer {
+            buf: self,
+            pending: Vec::new(),
+        }
+    }
+}
+
+/// [`Read`] adapter serving out a [`QueryBuf`]'s records as a byte stream.
+///
+/// Holds only a byte cursor, never copying the buffer's contents up front, so
+/// `read_to_end`/[`io::copy`]/[`io::BufReader`] all work directly against it.
+pub struct Reader<'a, T> {
+    buf: &'a QueryBuf<T>,
+    offset: usize,
+}
+
+impl<T: SeqRecord + Record> Read for Reader<'_, T> {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        let bytes = Record::to_bytes_slice(self.buf.records());
+        let remaining = &bytes[self.offset..];
+
+        let n = remaining.len().min(out.len());
+        out[..n].copy_from_slice(&remaining[..n]);
+        self.offset += n;
+
+        Ok(n)
+    }
+}
+
+/// [`Write`] adapter filling a [`QueryBuf`] from a byte stream.
+///
+/// Bytes are accumulated until a full multiple of `T::size()` is buffered, then
+/// committed into the buffer via [`Record::copy_from_bytes_slice`] -- `pending`
+/// is a plain `Vec<u8>` with no alignment guarantee of its own, so this copies
+/// rather than casting in place; a trailing partial record is held back until
+/// more bytes arrive.
+pub struct Writer<'a, T> {
+    buf: &'a mut QueryBuf<T>,
+    pending: Vec<u8>,
+}
+
+impl<T: SeqRecord + Record> Write for Writer<'_, T> {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(bytes);
+
+        let size = T::size();
+        let whole = (self.pending.len() / size) * size;
+        if whole > 0 {
+            self.buf.extend(&Record::copy_from_bytes_slice(&self.pending[..whole]));
+            self.pending.drain(..whole);
+        }
+
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Flush every byte of `slices` to `writer` via [`Write::write_vectored`],
+/// retrying and advancing past however much was written until nothing is left.
+///
+/// Mirrors the advance dance `bytes::Buf::chunks_vectored`-style consumers need,
+/// since [`Write::write_vectored`] is free to report a short, partial write
+/// across any number of the given slices.
+///
+/// # Arguments
+///
+/// * `writer` - Destination to flush bytes to.
+/// * `slices` - Vectored view over the bytes to write, e.g. from [`QueryBuf::io_slices`].
+pub fn write_all_vectored<W: Write>(
+    writer: &mut W,
+    slices: &mut [IoSlice<'_>],
+) -> std::io::Result<()> {
+    let mut slices = slices;
+
+    while !slices.is_empty() {
+        let mut written = writer.write_vectored(slices)?;
+        if written == 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::WriteZero,
+                "failed to write whole buffer",
+            ));
+        }
+
+        // Drop every leading slice that was fully consumed.
+        let mut skip = 0;
+        while skip < slices.len() && written >= slices[skip].len() {
+            written -= slices[skip].len();
+            skip += 1;
+        }
+        slices = &mut slices[skip..];
+
+        // The new first slice, if any, may have been partially written.
+        if written > 0 {
+            let first = &mut slices[0];
+            *first = IoSlice::new(&first[written..]);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+#[cfg(any(feature = "zerocopy", feature = "bytemuck"))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "bytemuck")]
+    use bytemuck::{Pod, Zeroable};
+
+    #[cfg(feature = "zerocopy")]
+    use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+    const CAPACITY: usize = 1024;
+
+    #[cfg(feature = "zerocopy")]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, FromBytes, IntoBytes, Immutable, KnownLayout)]
+    struct Log(u64);
+
+    #[cfg(feature = "bytemuck")]
+    #[repr(transparent)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+    struct Log(u64);
+
+    impl SeqRecord for Log {
+        fn seq_no(&self) -> u64 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn io_slices_yields_one_contiguous_slice() {
+        let mut buf = QueryBuf::new(CAPACITY);
+        let records: Vec<_> = (1..=CAPACITY as u64).map(Log).collect();
+        buf.extend(&records);
+
+        let slices: Vec<_> = buf.io_slices().collect();
+        assert_eq!(slices.len(), 1);
+        assert_eq!(&*slices[0], Record::to_bytes_slice(&records));
+    }
+
+    #[test]
+    fn write_all_vectored_handles_short_writes() {
+        // Writer that only accepts a handful of bytes per call, forcing
+        // `write_all_vectored` to retry and advance across slices.
+        struct Flaky(Vec<u8>);
+
+        impl Write for Flaky {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                let n = buf.len().min(3);
+                self.0.extend_from_slice(&buf[..n]);
+                Ok(n)
+            }
+
+            fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> std::io::Result<usize> {
+                self.write(&bufs[0])
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut buf = QueryBuf::new(CAPACITY);
+        let records: Vec<_> = (1..=8u64).map(Log).collect();
+        buf.extend(&records);
+
+        let mut slices: Vec<_> = buf.io_slices().collect();
+        let mut writer = Flaky(Vec::new());
+        write_all_vectored(&mut writer, &mut slices).unwrap();
+
+        assert_eq!(writer.0, Record::to_bytes_slice(&records));
+    }
+
+    #[test]
+    fn reader_streams_records_as_bytes() {
+        let mut buf = QueryBuf::new(CAPACITY);
+        let records: Vec<_> = (1..=8u64).map(Log).collect();
+        buf.extend(&records);
+
+        let mut out = Vec::new();
+        buf.reader().read_to_end(&mut out).unwrap();
+        assert_eq!(out, Record::to_bytes_slice(&records));
+    }
+
+    #[test]
+    fn reader_fills_caller_buffer_across_multiple_reads() {
+        let mut buf = QueryBuf::new(CAPACITY);
+        buf.extend(&[Log(1), Log(2)]);
+
+        let mut reader = buf.reader();
+        let mut first = [0u8; 8];
+        assert_eq!(reader.read(&mut first).unwrap(), 8);
+        assert_eq!(&first[..], Record::to_bytes(&Log(1)));
+
+        let mut second = [0u8; 8];
+        assert_eq!(reader.read(&mut second).unwrap(), 8);
+        assert_eq!(&second[..], Record::to_bytes(&Log(2)));
+
+        assert_eq!(reader.read(&mut [0u8; 8]).unwrap(), 0);
+    }
+
+    #[test]
+    fn writer_commits_whole_records_and_buffers_trailing_bytes() {
+        let mut buf = QueryBuf::new(CAPACITY);
+        let records: Vec<_> = (1..=4u64).map(Log).collect();
+        let bytes = Record::to_bytes_slice(&records);
+        let size = Log::size();
+
+        let mut writer = buf.writer();
+
+        // Write a record and a half; the trailing half-record stays pending
+        // inside the writer, not yet visible in the buffer.
+        writer.write_all(&bytes[..size * 2 + size / 2]).unwrap();
+        assert_eq!(writer.buf.records(), &records[..2]);
+
+        // Finishing off the split record, plus the rest, completes the buffer.
+        writer.write_all(&bytes[size * 2 + size / 2..]).unwrap();
+        assert_eq!(writer.buf.records(), &records);
+    }
+}
@@ -0,0 +1,143 @@
+//! Definition of a [`Storage`] decorator that caps how many records can accumulate.
+
+use crate::{SeqRecord, Storage};
+
+/// [`Storage`] decorator that truncates `extend` to a configured bound instead
+/// of relying on the caller to uphold the usual `records.len() <= remaining()`
+/// invariant, inspired by [`bytes::buf::Limit`].
+///
+/// Useful when the batch size being appended isn't trusted, e.g. records
+/// sourced from a network peer that shouldn't be able to force unbounded
+/// growth just by sending an oversized batch.
+#[derive(Debug)]
+pub struct Limit<S> {
+    inner: S,
+    limit: usize,
+}
+
+impl<S> Limit<S> {
+    /// Wrap `inner`, refusing appends once `limit` records are held.
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - Storage engine to wrap.
+    /// * `limit` - Maximum number of records `inner` is allowed to hold through this wrapper.
+    pub fn new(inner: S, limit: usize) -> Self {
+        Self { inner, limit }
+    }
+
+    /// Currently configured limit.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Change the configured limit.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - New maximum number of records this wrapper will allow.
+    pub fn set_limit(&mut self, limit: usize) {
+        self.limit = limit;
+    }
+
+    /// Consume this wrapper, returning the storage engine it decorated.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<R: SeqRecord, S: Storage<Record = R>> Storage for Limit<S> {
+    type Record = R;
+
+    fn capacity(&self) -> usize {
+        self.inner.capacity().min(self.limit)
+    }
+
+    fn length(&self) -> usize {
+        self.inner.length()
+    }
+
+    fn remaining(&self) -> usize {
+        self.capacity().saturating_sub(self.length())
+    }
+
+    fn trim(&mut self, len: usize) {
+        self.inner.trim(len);
+    }
+
+    /// Append as many of `records` as fit under the configured limit, silently
+    /// truncating the rest rather than overflowing it.
+    fn extend(&mut self, records: &[R]) {
+        let allowed = self.remaining().min(records.len());
+        if allowed > 0 {
+            self.inner.extend(&records[..allowed]);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    fn records(&self) -> &[R] {
+        self.inner.records()
+    }
+}
+
+#[cfg(test)]
+#[cfg(any(feature = "zerocopy", feature = "bytemuck"))]
+mod tests {
+    use super::*;
+    use crate::VecStorage;
+
+    #[cfg(feature = "bytemuck")]
+    use bytemuck::{Pod, Zeroable};
+
+    #[cfg(feature = "zerocopy")]
+    use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+    #[cfg(feature = "zerocopy")]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, FromBytes, IntoBytes, Immutable, KnownLayout)]
+    struct Log(u64);
+
+    #[cfg(feature = "bytemuck")]
+    #[repr(transparent)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+    struct Log(u64);
+
+    impl SeqRecord for Log {
+        fn seq_no(&self) -> u64 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn extend_truncates_at_the_limit() {
+        let mut storage = Limit::new(VecStorage::new(1024), 4);
+
+        let records: Vec<_> = (1..=8u64).map(Log).collect();
+        storage.extend(&records);
+
+        assert_eq!(storage.length(), 4);
+        assert_eq!(storage.remaining(), 0);
+        assert_eq!(storage.records(), &records[..4]);
+    }
+
+    #[test]
+    fn capacity_is_capped_even_when_inner_has_more_room() {
+        let storage = Limit::new(VecStorage::<Log>::new(1024), 10);
+        assert_eq!(storage.capacity(), 10);
+    }
+
+    #[test]
+    fn raising_the_limit_frees_up_remaining() {
+        let mut storage = Limit::new(VecStorage::new(1024), 2);
+        storage.extend(&[Log(1), Log(2)]);
+        assert_eq!(storage.remaining(), 0);
+
+        storage.set_limit(4);
+        assert_eq!(storage.remaining(), 2);
+
+        storage.extend(&[Log(3), Log(4), Log(5)]);
+        assert_eq!(storage.records(), &[Log(1), Log(2), Log(3), Log(4)]);
+    }
+}
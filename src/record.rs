@@ -36,6 +36,40 @@ pub trait Record: Sized {
     ///
     /// * `bytes` - Bytes to transmute.
     fn from_bytes_slice(bytes: &[u8]) -> &[Self];
+
+    /// Copy `bytes` into a fresh, `Self`-aligned `Vec<Self>`, instead of casting
+    /// in place like [`Record::from_bytes_slice`].
+    ///
+    /// Use this when `bytes` came from something that makes no alignment promise
+    /// of its own -- a serde format's `Vec<u8>`, bytes just read off a socket --
+    /// since [`Record::from_bytes_slice`] assumes the slice is already aligned to
+    /// `Self` and panics otherwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes.len()` is not a multiple of [`Record::size`].
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Bytes to copy, not assumed to satisfy `Self`'s alignment.
+    fn copy_from_bytes_slice(bytes: &[u8]) -> Vec<Self> {
+        let size = Self::size();
+        assert_eq!(bytes.len() % size, 0, "byte length is not a multiple of the record size");
+        let count = bytes.len() / size;
+
+        let mut out = Vec::<Self>::with_capacity(count);
+
+        // Safety: `out` has capacity for `count` `Self`s, exactly `bytes.len()`
+        // bytes; every `Record` is backed by a blanket impl that requires any bit
+        // pattern to be a valid value of `Self` (`AnyBitPattern`/`FromBytes`), so
+        // copying these raw bytes in and taking ownership of them as `Self` is sound.
+        unsafe {
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), out.as_mut_ptr().cast::<u8>(), bytes.len());
+            out.set_len(count);
+        }
+
+        out
+    }
 }
 
 // There will be conflicting implementations if both features are enabled.
@@ -135,6 +169,23 @@ mod tests {
             assert_eq!(records, returned);
         });
     }
+
+    #[test]
+    fn round_trip_record_copy_from_bytes_slice() {
+        check!().with_type::<Vec<Log>>().for_each(|records| {
+            // Copy into a byte buffer that's only ever guaranteed to be
+            // byte-aligned, same as a deserializer's `Vec<u8>` would be.
+            let mut bytes: Vec<u8> = Log::to_bytes_slice(records).to_vec();
+
+            let returned = Log::copy_from_bytes_slice(&bytes);
+            assert_eq!(records, &returned);
+
+            // The copy doesn't borrow from `bytes`, so mutating it afterwards
+            // doesn't affect the already-copied records.
+            bytes.fill(0);
+            assert_eq!(records, &returned);
+        });
+    }
 }
 
 #[cfg(test)]
@@ -186,4 +237,21 @@ mod tests {
             assert_eq!(records, returned);
         });
     }
+
+    #[test]
+    fn round_trip_record_copy_from_bytes_slice() {
+        check!().with_type::<Vec<Log>>().for_each(|records| {
+            // Copy into a byte buffer that's only ever guaranteed to be
+            // byte-aligned, same as a deserializer's `Vec<u8>` would be.
+            let mut bytes: Vec<u8> = Log::to_bytes_slice(records).to_vec();
+
+            let returned = Log::copy_from_bytes_slice(&bytes);
+            assert_eq!(records, &returned);
+
+            // The copy doesn't borrow from `bytes`, so mutating it afterwards
+            // doesn't affect the already-copied records.
+            bytes.fill(0);
+            assert_eq!(records, &returned);
+        });
+    }
 }
@@ -1,7 +1,7 @@
 //! Definition of a ring buffer.
 
 use crate::{OffHeapStorage, OnHeapStorage, QueryBuf, SeqRecord, Storage, VecStorage};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, btree_map};
 use thiserror::Error;
 
 /// Type alias for a [`SeqRing`] backed by [`VecStorage`].
@@ -18,6 +18,37 @@ pub type OffHeapSeqRing<T> = SeqRing<OffHeapStorage<T>>;
 pub enum AppendError {
     #[error("Records appended out of sequence. Prev: {0}, Record: {1}")]
     Sequence(u64, u64),
+
+    #[error("Too many holes being tracked for out-of-order reassembly, at most {0} allowed")]
+    TooManyHoles(usize),
+}
+
+/// Maximum number of holes [`SeqRing::append_reassemble`] tracks at once, bounding how
+/// much staging memory a badly-lagging or adversarial producer can force the ring to hold.
+const MAX_HOLES: usize = 1024;
+
+/// A run of `hole_size` missing sequence numbers immediately followed by a run of
+/// `data_size` present ones, relative to wherever the previous [`Contig`] in the list
+/// leaves off. The first entry in a ring's contig list always starts right after its
+/// committed head, i.e. `prev_seq_no`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Contig {
+    hole_size: u64,
+    data_size: u64,
+}
+
+/// Snapshot of a [`SeqRing`]'s occupancy and addressable sequence range, returned by
+/// [`SeqRing::limits`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferLimits {
+    /// Number of records currently held.
+    pub len: usize,
+    /// Total record capacity across every slot, occupied or free.
+    pub capacity: usize,
+    /// Sequence number of the most recently committed record.
+    pub head_seq_no: u64,
+    /// Sequence number of the oldest record still retrievable.
+    pub tail_seq_no: u64,
 }
 
 /// An in-memory Ring buffer that holds [`SeqRecord`]s.
@@ -26,10 +57,20 @@ pub enum AppendError {
 /// * Performs strict sequence validations against appended records.
 /// * Can query from logical positions in ring buffer or via record sequence numbers.
 #[derive(Debug)]
-pub struct SeqRing<T> {
+pub struct SeqRing<T: Storage> {
     prev_seq_no: u64,
     free_slots: Vec<T>,
     slots: BTreeMap<u64, T>,
+
+    // Hole/data contig list and staging area backing `append_reassemble`. Empty of
+    // any real gap, `contigs` is a single zero/zero entry; see `Contig`.
+    contigs: Vec<Contig>,
+    staging: BTreeMap<u64, T::Record>,
+
+    // Capacity callers are steering towards via `reserve`/`shrink_to`, distinct
+    // from `capacity()` (what's actually allocated right now). Purely
+    // informational: adjusting it doesn't resize anything on its own.
+    target_capacity: usize,
 }
 
 impl<R: SeqRecord + Copy> VecSeqRing<R> {
@@ -57,6 +98,24 @@ impl<R: SeqRecord + Copy> VecSeqRing<R> {
         // Build and return a new Ring.
         SeqRing::from_parts(free_slots, prev_seq_no)
     }
+
+    /// Grow this ring buffer by allocating `additional_slots` more slots,
+    /// the same size as the slots already in use, and adding them to the
+    /// free list.
+    ///
+    /// Does not touch [`SeqRing::target_capacity`]; callers driving an
+    /// adaptive sizing policy are expected to keep the two in sync
+    /// themselves.
+    ///
+    /// # Arguments
+    ///
+    /// * `additional_slots` - Number of new slots to make available.
+    pub fn reserve(&mut self, additional_slots: usize) {
+        let slot_capacity = self.slot_capacity();
+        self.free_slots.extend(
+            std::iter::repeat_with(|| VecStorage::new(slot_capacity)).take(additional_slots),
+        );
+    }
 }
 
 impl<R: SeqRecord> OnHeapSeqRing<R> {
@@ -84,6 +143,24 @@ impl<R: SeqRecord> OnHeapSeqRing<R> {
         // Build and return a new Ring.
         SeqRing::from_parts(free_slots, prev_seq_no)
     }
+
+    /// Grow this ring buffer by allocating `additional_slots` more slots,
+    /// the same size as the slots already in use, and adding them to the
+    /// free list.
+    ///
+    /// Does not touch [`SeqRing::target_capacity`]; callers driving an
+    /// adaptive sizing policy are expected to keep the two in sync
+    /// themselves.
+    ///
+    /// # Arguments
+    ///
+    /// * `additional_slots` - Number of new slots to make available.
+    pub fn reserve(&mut self, additional_slots: usize) {
+        let slot_capacity = self.slot_capacity();
+        self.free_slots.extend(
+            std::iter::repeat_with(|| OnHeapStorage::new(slot_capacity)).take(additional_slots),
+        );
+    }
 }
 
 impl<R: SeqRecord> OffHeapSeqRing<R> {
@@ -111,6 +188,24 @@ impl<R: SeqRecord> OffHeapSeqRing<R> {
         // Build and return a new Ring.
         SeqRing::from_parts(free_slots, prev_seq_no)
     }
+
+    /// Grow this ring buffer by allocating `additional_slots` more slots,
+    /// the same size as the slots already in use, and adding them to the
+    /// free list.
+    ///
+    /// Does not touch [`SeqRing::target_capacity`]; callers driving an
+    /// adaptive sizing policy are expected to keep the two in sync
+    /// themselves.
+    ///
+    /// # Arguments
+    ///
+    /// * `additional_slots` - Number of new slots to make available.
+    pub fn reserve(&mut self, additional_slots: usize) {
+        let slot_capacity = self.slot_capacity();
+        self.free_slots.extend(
+            std::iter::repeat_with(|| OffHeapStorage::new(slot_capacity)).take(additional_slots),
+        );
+    }
 }
 
 impl<R: SeqRecord, T: Storage<Record = R>> SeqRing<T> {
@@ -123,6 +218,9 @@ impl<R: SeqRecord, T: Storage<Record = R>> SeqRing<T> {
     fn from_parts(mut free_slots: Vec<T>, prev_seq_no: u64) -> Self {
         // Initialize latest slot in the ring buffer.
         let storage = free_slots.pop().expect("Ring has > 1 slots");
+        let slot_capacity = storage.capacity();
+        let target_capacity = (free_slots.len() + 1) * slot_capacity;
+
         let mut slots = BTreeMap::new();
         slots.insert(prev_seq_no, storage);
 
@@ -130,6 +228,124 @@ impl<R: SeqRecord, T: Storage<Record = R>> SeqRing<T> {
             slots,
             free_slots,
             prev_seq_no,
+            contigs: vec![Contig {
+                hole_size: 0,
+                data_size: 0,
+            }],
+            staging: BTreeMap::new(),
+            target_capacity,
+        }
+    }
+
+    /// Record capacity of an individual slot, occupied or free.
+    fn slot_capacity(&self) -> usize {
+        self.slots
+            .values()
+            .next()
+            .expect("Ring has > 1 slot")
+            .capacity()
+    }
+
+    /// Number of records currently held in the ring buffer.
+    pub fn len(&self) -> usize {
+        self.slots.values().map(Storage::length).sum()
+    }
+
+    /// true if this ring buffer holds no records, false otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Total record capacity across every slot, occupied or free.
+    pub fn capacity(&self) -> usize {
+        (self.free_slots.len() + self.slots.len()) * self.slot_capacity()
+    }
+
+    /// Capacity callers are currently steering this ring buffer towards, via
+    /// an adaptive sizing policy built on top of [`SeqRing::reserve`] and
+    /// [`SeqRing::shrink_to`].
+    ///
+    /// Defaults to the capacity requested at construction and is otherwise
+    /// just a value set by [`SeqRing::set_target_capacity`]; it has no effect
+    /// on its own, it does not reserve or shrink anything.
+    pub fn target_capacity(&self) -> usize {
+        self.target_capacity
+    }
+
+    /// Record the capacity a caller wants this ring buffer to grow or shrink
+    /// towards, without touching any storage yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Capacity to steer towards on a future [`SeqRing::reserve`]
+    ///   or [`SeqRing::shrink_to`].
+    pub fn set_target_capacity(&mut self, target: usize) {
+        self.target_capacity = target;
+    }
+
+    /// Shrink the number of slots backing this ring buffer down to `slots`,
+    /// following the TCP receive-buffer model: a prior burst that forced
+    /// growth can be released once it's over without disturbing live data,
+    /// as long as enough of the growth is still sitting unused in the free
+    /// list.
+    ///
+    /// Unused, free slots are dropped first, since releasing them costs
+    /// nothing. If that isn't enough to reach `slots`, the oldest occupied
+    /// slots are evicted next, advancing the effective tail and discarding
+    /// whatever records they held, exactly as [`SeqRing::append`] does when
+    /// it needs to reclaim storage and finds the free list empty. At least
+    /// one occupied slot is always kept, since every other operation on this
+    /// ring buffer assumes `slots` is never empty.
+    ///
+    /// Does not touch [`SeqRing::target_capacity`]; callers driving an
+    /// adaptive sizing policy are expected to keep the two in sync
+    /// themselves.
+    ///
+    /// # Arguments
+    ///
+    /// * `slots` - Number of slots, occupied or free, to shrink down to.
+    pub fn shrink_to(&mut self, slots: usize) {
+        let total = |this: &Self| this.free_slots.len() + this.slots.len();
+
+        while total(self) > slots && !self.free_slots.is_empty() {
+            self.free_slots.pop();
+        }
+
+        while total(self) > slots && self.slots.len() > 1 {
+            self.slots.pop_first();
+        }
+    }
+
+    /// Number of records that can be appended before the oldest slot gets evicted.
+    pub fn window(&self) -> usize {
+        self.capacity() - self.len()
+    }
+
+    /// Sequence number of the oldest record still retrievable from this ring buffer.
+    ///
+    /// Equal to `head_seq_no` if no records have been appended yet.
+    fn tail_seq_no(&self) -> u64 {
+        if self.is_empty() {
+            return self.prev_seq_no;
+        }
+
+        // Every slot is keyed by the seq_no of the record right before its
+        // first one, so the oldest slot's key + 1 is the tail.
+        let (oldest_key, _) = self.slots.first_key_value().expect("Ring has > 1 slot");
+        oldest_key + 1
+    }
+
+    /// Snapshot of this ring buffer's current occupancy and addressable sequence range.
+    ///
+    /// Lets a consumer detect when its last-seen `seq_no` has already been evicted
+    /// (i.e. `seq_no < limits.tail_seq_no`) before calling [`SeqRing::query_after`],
+    /// which otherwise silently clamps to the oldest slot.
+    pub fn limits(&self) -> BufferLimits {
+        BufferLimits {
+            len: self.len(),
+            capacity: self.capacity(),
+            head_seq_no: self.prev_seq_no,
+            tail_seq_no: self.tail_seq_no(),
         }
     }
 
@@ -202,6 +418,200 @@ impl<R: SeqRecord, T: Storage<Record = R>> SeqRing<T> {
         Ok(()) // Records appended successfully.
     }
 
+    /// Append records that may arrive out of order, buffering anything ahead of a gap
+    /// instead of rejecting it, and committing into real slots via [`SeqRing::append`]
+    /// once the buffered prefix becomes contiguous again. Mirrors a TCP-style
+    /// reassembly buffer.
+    ///
+    /// A batch that lands entirely at or below `prev_seq_no` is treated as a stale
+    /// retransmit of data already committed and is silently dropped, rather than
+    /// returning [`AppendError::Sequence`] like [`SeqRing::append`] does. The number
+    /// of holes tracked while waiting for gaps to fill is bounded; once exceeded this
+    /// returns [`AppendError::TooManyHoles`] instead of growing unbounded.
+    ///
+    /// # Arguments
+    ///
+    /// * `records` - A contiguous run of records to reassemble, in ascending `seq_no` order.
+    pub fn append_reassemble(&mut self, records: &[R]) -> Result<(), AppendError>
+    where
+        R: Copy,
+    {
+        // Early return if there are no records to reassemble.
+        let Some(last) = records.last() else {
+            return Ok(());
+        };
+
+        // Entirely below the committed head: a stale retransmit, nothing to do.
+        if last.seq_no() <= self.prev_seq_no {
+            return Ok(());
+        }
+
+        // Drop whatever overlaps what's already committed; only the tail matters.
+        let skip = records
+            .iter()
+            .take_while(|record| record.seq_no() <= self.prev_seq_no)
+            .count();
+        let records = &records[skip..];
+
+        let Some(first) = records.first() else {
+            return Ok(());
+        };
+
+        // Stage the records, then fold the run they span into the contig list.
+        for record in records {
+            self.staging.insert(record.seq_no(), *record);
+        }
+
+        let start = first.seq_no() - self.prev_seq_no - 1;
+        let end = start + records.len() as u64;
+        self.add_segment(start, end)?;
+
+        // Commit every prefix that has become fully contiguous.
+        while self.contigs[0].hole_size == 0 && self.contigs[0].data_size > 0 {
+            let data_size = self.contigs[0].data_size as usize;
+            let keys: Vec<u64> = self.staging.keys().take(data_size).copied().collect();
+            let committed: Vec<R> = keys
+                .iter()
+                .map(|key| {
+                    self.staging
+                        .remove(key)
+                        .expect("Key was just read from staging")
+                })
+                .collect();
+
+            self.append(&committed)?;
+            self.contigs.remove(0);
+
+            if self.contigs.is_empty() {
+                self.contigs.push(Contig {
+                    hole_size: 0,
+                    data_size: 0,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Fold a freshly staged `[start, end)` run, offsets relative to the committed
+    /// head, into the hole/data contig list: shrinks whichever hole(s) it lands in,
+    /// growing the adjacent data run(s), and coalesces with the following data run
+    /// wherever a hole closes completely.
+    ///
+    /// A run can straddle a hole/data boundary (e.g. a retransmit that both fills a
+    /// hole and re-covers data that's already staged) or even span several contigs
+    /// at once, not just land cleanly inside a single hole; each contig the run
+    /// touches is folded in turn rather than bailing out early.
+    fn add_segment(&mut self, start: u64, end: u64) -> Result<(), AppendError> {
+        let mut pos = 0u64;
+        let mut i = 0;
+
+        while i < self.contigs.len() {
+            let hole_start = pos;
+            let hole_end = pos + self.contigs[i].hole_size;
+            let data_size = self.contigs[i].data_size;
+            let data_end = hole_end + data_size;
+
+            // The run hasn't reached this contig (or anything after it) yet.
+            if end <= hole_start {
+                return Ok(());
+            }
+
+            // The run is entirely past this contig: already-closed ground, or data
+            // that's already staged/committed from it. Move on to the next one.
+            if start >= data_end {
+                pos = data_end;
+                i += 1;
+                continue;
+            }
+
+            // The run overlaps this contig's hole and/or data run. Only the part
+            // that falls inside the hole is new information; anything from
+            // `hole_end` onward overlaps data that's already staged (e.g. a
+            // retransmit racing its own original), so there's nothing to do there.
+            let fill_start = start.max(hole_start);
+            let fill_end = end.min(hole_end);
+
+            if fill_end <= fill_start {
+                // Doesn't actually reach into the hole, only the data run after it.
+                pos = data_end;
+                i += 1;
+                continue;
+            }
+
+            let before = fill_start - hole_start;
+            let after = hole_end - fill_end;
+            let filled = fill_end - fill_start;
+
+            if after == 0 {
+                // Hole closes completely: coalesce with the data run that already
+                // followed it. The run may still extend further, into subsequent
+                // contigs, so keep going rather than returning.
+                self.contigs[i] = Contig {
+                    hole_size: before,
+                    data_size: filled + data_size,
+                };
+                pos = data_end;
+                i += 1;
+            } else {
+                // The run ends inside this hole, so it's fully accounted for here.
+                if self.contigs.len() >= MAX_HOLES {
+                    return Err(AppendError::TooManyHoles(MAX_HOLES));
+                }
+
+                self.contigs[i] = Contig {
+                    hole_size: before,
+                    data_size: filled,
+                };
+                self.contigs.insert(
+                    i + 1,
+                    Contig {
+                        hole_size: after,
+                        data_size,
+                    },
+                );
+
+                return Ok(());
+            }
+        }
+
+        // Nothing left of the run lands past the frontier of every tracked contig.
+        if end <= pos {
+            return Ok(());
+        }
+
+        // Any part of the run at or before the frontier was already folded into the
+        // loop above; only the genuinely new tail, starting at the frontier, is left.
+        let start = start.max(pos);
+        let last = self
+            .contigs
+            .last_mut()
+            .expect("Contigs always has at least one entry");
+
+        if start == pos {
+            // Directly continues the trailing data run, no new hole opens.
+            last.data_size += end - start;
+        } else if last.hole_size == 0 && last.data_size == 0 {
+            // Still the initial idle placeholder; fill it in directly instead of
+            // leaving it as a permanent empty entry ahead of the real one.
+            *last = Contig {
+                hole_size: start - pos,
+                data_size: end - start,
+            };
+        } else {
+            if self.contigs.len() >= MAX_HOLES {
+                return Err(AppendError::TooManyHoles(MAX_HOLES));
+            }
+
+            self.contigs.push(Contig {
+                hole_size: start - pos,
+                data_size: end - start,
+            });
+        }
+
+        Ok(())
+    }
+
     /// Query for records from the beginning.
     ///
     /// * Records returned are sorted in ascending order of their sequence numbers.
@@ -318,6 +728,172 @@ impl<R: SeqRecord, T: Storage<Record = R>> SeqRing<T> {
             buf.extend(copy);
         }
     }
+
+    /// Query for the most recent records available, without needing to know
+    /// the current head's sequence number.
+    ///
+    /// * Records returned are sorted in ascending order of their sequence numbers.
+    /// * buf is cleared of any existing records to make space for records from query.
+    /// * buf is filled backward from the newest record, so if history is larger
+    ///   than buf's capacity the records dropped are the oldest ones, not the newest.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - Buffer to copy records into.
+    pub fn query_latest(&self, buf: &mut QueryBuf<R>) {
+        // Querying for everything before one past the head is exactly every
+        // record this ring buffer can still produce.
+        self.query_before(self.prev_seq_no + 1, buf);
+    }
+
+    /// Query for records with a sequence number before a specific sequence number,
+    /// for paging backward through history.
+    ///
+    /// * Records returned are sorted in ascending order of their sequence numbers.
+    /// * buf is cleared of any existing records to make space for records from query.
+    /// * buf is filled backward from just before `seq_no`, so if that much history
+    ///   doesn't fit, the records dropped are the oldest ones, not the newest.
+    ///
+    /// # Arguments
+    ///
+    /// * `seq_no` - Only records appended strictly before this sequence number are yielded.
+    /// * `buf` - Buffer to copy records into.
+    pub fn query_before(&self, seq_no: u64, buf: &mut QueryBuf<R>) {
+        // Clear records to make space for the new query.
+        buf.clear();
+
+        // Return early if nothing retrievable is old enough to be before seq_no.
+        if seq_no <= self.tail_seq_no() {
+            return;
+        }
+
+        // Fetch the boundary slot to begin iterating backward from.
+        let (boundary_seq_no, _) = self
+            .slots
+            .range(..seq_no)
+            .next_back()
+            .expect("seq_no > tail_seq_no, so at least the oldest slot qualifies");
+
+        // Walk slots oldest-to-newest up to and including the boundary slot,
+        // but in reverse, so the newest records are considered first. Collect
+        // chunks newest-first respecting buf.remaining(), then replay them
+        // oldest-first so buf ends up in the ascending order every other
+        // query method guarantees.
+        let mut remaining = buf.remaining();
+        let mut chunks: Vec<&[R]> = Vec::new();
+
+        for (slot_seq_no, slot) in self.slots.range(..=boundary_seq_no).rev() {
+            if remaining == 0 {
+                break;
+            }
+
+            let records = slot.records();
+            let records = if slot_seq_no == boundary_seq_no {
+                // Trim to just the records strictly before seq_no.
+                let index = records
+                    .binary_search_by_key(&seq_no, SeqRecord::seq_no)
+                    .unwrap_or_else(|index| index);
+
+                // Safety: index <= records.len(), as returned by binary_search_by_key.
+                unsafe { records.split_at_unchecked(index).0 }
+            } else {
+                records
+            };
+
+            // Figure out the trailing range of records to copy.
+            let take = std::cmp::min(remaining, records.len());
+            let (_, tail) = unsafe { records.split_at_unchecked(records.len() - take) };
+
+            remaining -= tail.len();
+            chunks.push(tail);
+        }
+
+        // Replay the collected chunks oldest-first.
+        for chunk in chunks.into_iter().rev() {
+            buf.extend(chunk);
+        }
+    }
+
+    /// Borrowing counterpart to [`SeqRing::query_from_trim`]: yields `&[R]` slices
+    /// that borrow directly into each slot's storage instead of copying records
+    /// into a [`QueryBuf`].
+    ///
+    /// * Slices are yielded in ascending order of sequence number.
+    /// * Each slice is a contiguous run within a single slot; a caller wanting
+    ///   the whole buffer as one logical stream just needs to consume them in order.
+    pub fn iter_from(&self) -> Iter<'_, R, T> {
+        Iter {
+            slots: Some(self.slots.range(..)),
+            skip: 0,
+        }
+    }
+
+    /// Borrowing counterpart to [`SeqRing::query_after`]: yields `&[R]` slices
+    /// that borrow directly into each slot's storage instead of copying records
+    /// into a [`QueryBuf`].
+    ///
+    /// * Slices are yielded in ascending order of sequence number.
+    /// * The returned iterator yields nothing if `seq_no` has not yet been appended.
+    ///
+    /// # Arguments
+    ///
+    /// * `seq_no` - Only records appended after this sequence number are yielded.
+    pub fn iter_after(&self, seq_no: u64) -> Iter<'_, R, T> {
+        // Return early if seq_no is not yet appended.
+        if self.prev_seq_no <= seq_no {
+            return Iter {
+                slots: None,
+                skip: 0,
+            };
+        }
+
+        // Fetch the starting slot to begin iteration. See `query_after` for how
+        // slot keys line up against the seq_no being searched for.
+        let (start_seq_no, start_slot) = self
+            .slots
+            .range(..=seq_no)
+            .next_back()
+            .unwrap_or_else(|| self.slots.first_key_value().expect("Ring has > 1 slot"));
+
+        // Figure out the index within the starting slot to skip to.
+        let records = start_slot.records();
+        let skip = match records.binary_search_by_key(&seq_no, SeqRecord::seq_no) {
+            // Means record was not found, but would have been in this index.
+            // So we can start from this index.
+            Err(index) => index,
+
+            // Means record is found in this index, so we want to start from next index.
+            Ok(index) => index + 1,
+        };
+
+        Iter {
+            slots: Some(self.slots.range(start_seq_no..)),
+            skip,
+        }
+    }
+}
+
+/// Iterator returned by [`SeqRing::iter_from`] and [`SeqRing::iter_after`] that
+/// borrows directly into each slot's storage, yielding a contiguous `&[R]` per
+/// slot instead of copying records into a [`QueryBuf`].
+pub struct Iter<'a, R: SeqRecord + 'a, T: Storage<Record = R>> {
+    slots: Option<btree_map::Range<'a, u64, T>>,
+    skip: usize,
+}
+
+impl<'a, R: SeqRecord + 'a, T: Storage<Record = R>> Iterator for Iter<'a, R, T> {
+    type Item = &'a [R];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (_, slot) = self.slots.as_mut()?.next()?;
+
+        // Only the first slot yielded needs to skip past already-seen records.
+        let skip = std::mem::take(&mut self.skip);
+        let records = slot.records();
+
+        // Safety: `skip` was computed from a binary search into this exact slot.
+        Some(unsafe { records.split_at_unchecked(skip).1 })
+    }
 }
 
 #[cfg(test)]
@@ -430,6 +1006,167 @@ mod tests {
         Ok(())
     }
 
+    #[rstest]
+    #[case(vec_ring(0))]
+    #[case(on_heap_ring(0))]
+    #[case(off_heap_ring(0))]
+    fn append_reassemble<S: Storage<Record = Log>>(
+        #[case] mut ring: SeqRing<S>,
+    ) -> Result<(), AppendError> {
+        let records: Vec<_> = (1..=300u64).map(Log).collect();
+
+        // Deliver every batch of 3 records out of order; the ring should still
+        // reassemble them back into the original sequence once gaps are filled.
+        for chunk in records.chunks(3) {
+            ring.append_reassemble(&[chunk[2]])?;
+            ring.append_reassemble(&[chunk[0]])?;
+            ring.append_reassemble(&[chunk[1]])?;
+        }
+
+        let mut buf = QueryBuf::new(records.len());
+        ring.query_from_trim(&mut buf);
+        assert_eq!(buf.records(), &records);
+
+        // A stale retransmit of already-committed records is a silent no-op.
+        ring.append_reassemble(&[Log(1), Log(2)])?;
+        ring.query_from_trim(&mut buf);
+        assert_eq!(buf.records(), &records);
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(vec_ring(0))]
+    #[case(on_heap_ring(0))]
+    #[case(off_heap_ring(0))]
+    fn append_reassemble_overlapping_retransmit_closes_hole<S: Storage<Record = Log>>(
+        #[case] mut ring: SeqRing<S>,
+    ) -> Result<(), AppendError> {
+        // Receive [3, 4] first, opening a hole for [1, 2]; then a retransmit of
+        // [1, 2, 3] both fills that hole and re-covers the already-staged 3. This
+        // must still close the hole and commit, rather than staging forever.
+        ring.append_reassemble(&[Log(3), Log(4)])?;
+        ring.append_reassemble(&[Log(1), Log(2), Log(3)])?;
+
+        let mut buf = QueryBuf::new(4);
+        ring.query_from_trim(&mut buf);
+        assert_eq!(buf.records(), &[Log(1), Log(2), Log(3), Log(4)]);
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(vec_ring(0))]
+    fn append_reassemble_too_many_holes<S: Storage<Record = Log>>(#[case] mut ring: SeqRing<S>) {
+        // Leave every other sequence number missing, opening a fresh hole each
+        // time, until the cap on tracked holes is exceeded.
+        for seq_no in (1..=(2 * (MAX_HOLES as u64 + 2))).step_by(2) {
+            if let Err(err) = ring.append_reassemble(&[Log(seq_no)]) {
+                assert!(matches!(err, AppendError::TooManyHoles(n) if n == MAX_HOLES));
+                return;
+            }
+        }
+
+        panic!("Expected AppendError::TooManyHoles before exhausting the loop");
+    }
+
+    #[rstest]
+    #[case(vec_ring(0))]
+    #[case(on_heap_ring(0))]
+    #[case(off_heap_ring(0))]
+    fn introspection<S: Storage<Record = Log>>(
+        #[case] mut ring: SeqRing<S>,
+    ) -> Result<(), AppendError> {
+        // Fresh ring buffer, nothing appended yet.
+        assert!(ring.is_empty());
+        assert_eq!(ring.len(), 0);
+        assert_eq!(ring.capacity(), MAX_CAPACITY);
+        assert_eq!(ring.window(), MAX_CAPACITY);
+        assert_eq!(
+            ring.limits(),
+            BufferLimits {
+                len: 0,
+                capacity: MAX_CAPACITY,
+                head_seq_no: 0,
+                tail_seq_no: 0,
+            }
+        );
+
+        // Append some records, but not enough to evict anything yet.
+        let records: Vec<_> = (1..=256u64).map(Log).collect();
+        ring.append(&records)?;
+
+        assert!(!ring.is_empty());
+        assert_eq!(ring.len(), records.len());
+        assert_eq!(ring.capacity(), MAX_CAPACITY);
+        assert_eq!(ring.window(), MAX_CAPACITY - records.len());
+        assert_eq!(
+            ring.limits(),
+            BufferLimits {
+                len: records.len(),
+                capacity: MAX_CAPACITY,
+                head_seq_no: records.len() as u64,
+                tail_seq_no: 1,
+            }
+        );
+
+        // Fill the ring past capacity, forcing the oldest slot to be evicted.
+        let more: Vec<_> = (257..=(MAX_CAPACITY as u64 + SLOT_CAPACITY as u64 + 1))
+            .map(Log)
+            .collect();
+        ring.append(&more)?;
+
+        assert_eq!(ring.capacity(), MAX_CAPACITY);
+        assert!(ring.len() < MAX_CAPACITY);
+        assert_eq!(ring.window(), ring.capacity() - ring.len());
+        let limits = ring.limits();
+        assert!(limits.tail_seq_no > 1);
+        assert_eq!(limits.head_seq_no, MAX_CAPACITY as u64 + SLOT_CAPACITY as u64 + 1);
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(vec_ring(0))]
+    #[case(on_heap_ring(0))]
+    #[case(off_heap_ring(0))]
+    fn shrink_to<S: Storage<Record = Log>>(#[case] mut ring: SeqRing<S>) -> Result<(), AppendError> {
+        // Defaults to the capacity requested at construction.
+        assert_eq!(ring.target_capacity(), MAX_CAPACITY);
+
+        // Adjusting target_capacity on its own has no effect on allocated storage.
+        ring.set_target_capacity(SLOT_CAPACITY);
+        assert_eq!(ring.target_capacity(), SLOT_CAPACITY);
+        assert_eq!(ring.capacity(), MAX_CAPACITY);
+
+        // Shrinking to at or above the current slot count is a no-op.
+        ring.shrink_to(SLOTS + 1);
+        assert_eq!(ring.capacity(), MAX_CAPACITY);
+        ring.shrink_to(SLOTS);
+        assert_eq!(ring.capacity(), MAX_CAPACITY);
+
+        // Shrinking below the current slot count first drops unused, free slots.
+        ring.shrink_to(SLOTS - 1);
+        assert_eq!(ring.capacity(), (SLOTS - 1) * SLOT_CAPACITY);
+        assert!(ring.is_empty());
+
+        // Fill every remaining slot, then shrink further: free slots are gone, so
+        // this evicts the oldest occupied slot, discarding the records it held.
+        let records: Vec<_> = (1..=((SLOTS - 1) * SLOT_CAPACITY) as u64).map(Log).collect();
+        ring.append(&records)?;
+        assert_eq!(ring.len(), records.len());
+
+        ring.shrink_to(1);
+        assert_eq!(ring.capacity(), SLOT_CAPACITY);
+        assert!(ring.len() < records.len());
+
+        // Never shrinks away the one slot every other operation relies on.
+        ring.shrink_to(0);
+        assert_eq!(ring.capacity(), SLOT_CAPACITY);
+
+        Ok(())
+    }
+
     #[rstest]
     #[case(vec_ring(0))]
     #[case(on_heap_ring(0))]
@@ -502,4 +1239,131 @@ mod tests {
 
         Ok(())
     }
+
+    #[rstest]
+    #[case(vec_ring(0))]
+    #[case(on_heap_ring(0))]
+    #[case(off_heap_ring(0))]
+    fn query_latest<S: Storage<Record = Log>>(
+        #[case] mut ring: SeqRing<S>,
+    ) -> Result<(), AppendError> {
+        let records: Vec<_> = (1..=MAX_CAPACITY as u64).map(Log).collect();
+        ring.append(&records)?;
+
+        // Query for records with different batch sizes.
+        for batch_size in (256..=MAX_CAPACITY).step_by(256) {
+            let mut buf = QueryBuf::new(batch_size);
+            ring.query_latest(&mut buf);
+
+            // Newest `batch_size` records, still in ascending order.
+            let start = records.len() - batch_size.min(records.len());
+            assert_eq!(&records[start..], buf.records());
+        }
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(vec_ring(0), 1)]
+    #[case(on_heap_ring(0), 1)]
+    #[case(off_heap_ring(0), 1)]
+    #[case(vec_ring(0), 2)]
+    #[case(on_heap_ring(0), 2)]
+    #[case(off_heap_ring(0), 2)]
+    #[case(vec_ring(0), 3)]
+    #[case(on_heap_ring(0), 3)]
+    #[case(off_heap_ring(0), 3)]
+    fn query_before<S: Storage<Record = Log>>(
+        #[case] mut ring: SeqRing<S>,
+        #[case] skip_size: usize,
+    ) -> Result<(), AppendError> {
+        // Test records appended into the ring buffer.
+        let records: Vec<_> = (1..=MAX_CAPACITY as u64)
+            .map(|seq_no| seq_no * skip_size as u64)
+            .map(Log)
+            .collect();
+
+        // Append all the test records into ring buffer.
+        ring.append(&records)?;
+
+        // Query for records with different batch sizes.
+        for batch_size in (256..=MAX_CAPACITY).step_by(256) {
+            // Buffer to query for records from the ring.
+            let mut buf = QueryBuf::new(batch_size);
+
+            // Query before every sequence number.
+            for seq_no in 1..=((MAX_CAPACITY * skip_size) as u64 + 1) {
+                ring.query_before(seq_no, &mut buf);
+
+                // Make sure expected records were returned.
+                let end = records.partition_point(|log| log.seq_no() < seq_no);
+                let start = end.saturating_sub(batch_size);
+                assert_eq!(&records[start..end], buf.records());
+            }
+
+            // Nothing is before the very first retrievable record.
+            ring.query_before(records[0].seq_no(), &mut buf);
+            assert_eq!(buf.records(), &[]);
+        }
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(vec_ring(0))]
+    #[case(on_heap_ring(0))]
+    #[case(off_heap_ring(0))]
+    fn iter_from<S: Storage<Record = Log>>(
+        #[case] mut ring: SeqRing<S>,
+    ) -> Result<(), AppendError> {
+        let records: Vec<_> = (1..=MAX_CAPACITY as u64).map(Log).collect();
+        ring.append(&records)?;
+
+        let borrowed: Vec<_> = ring.iter_from().flatten().copied().collect();
+        assert_eq!(borrowed, records);
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(vec_ring(0))]
+    #[case(on_heap_ring(0))]
+    #[case(off_heap_ring(0))]
+    fn iter_after<S: Storage<Record = Log>>(
+        #[case] mut ring: SeqRing<S>,
+    ) -> Result<(), AppendError> {
+        let records: Vec<_> = (1..=MAX_CAPACITY as u64).map(Log).collect();
+        ring.append(&records)?;
+
+        // Must match `query_after` for the same seq_no, just borrowed instead of copied.
+        for seq_no in 0..=MAX_CAPACITY as u64 {
+            let mut buf = QueryBuf::new(MAX_CAPACITY);
+            ring.query_after(seq_no, &mut buf);
+
+            let borrowed: Vec<_> = ring.iter_after(seq_no).flatten().copied().collect();
+            assert_eq!(borrowed, buf.records());
+        }
+
+        // Nothing to iterate past the latest committed seq_no.
+        assert_eq!(ring.iter_after(MAX_CAPACITY as u64).flatten().count(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn reserve() {
+        // Same underlying slot allocation logic for every storage engine, so
+        // exercising one is enough; see `shrink_to` for the release half.
+        let mut ring = vec_ring(0);
+        assert_eq!(ring.capacity(), MAX_CAPACITY);
+
+        // New slots count towards capacity right away, not just once used.
+        ring.reserve(SLOTS);
+        assert_eq!(ring.capacity(), 2 * MAX_CAPACITY);
+
+        let records: Vec<_> = (1..=(2 * MAX_CAPACITY) as u64).map(Log).collect();
+        ring.append(&records).unwrap();
+        assert_eq!(ring.len(), records.len());
+        assert_eq!(ring.capacity(), 2 * MAX_CAPACITY);
+    }
 }
@@ -0,0 +1,144 @@
+//! Optional `serde` support for snapshotting record buffers.
+//!
+//! Analogous to the `src/serde.rs` module the `bytes` crate ships. Serializing
+//! copies the raw [`Record::to_bytes_slice`] bytes into a `Vec<u8>`, emitted as a
+//! sequence of byte values, plus a record count, so the serialize side is not
+//! zero-copy; deserializing validates the byte length is a multiple
+//! of `T::size()` before reconstructing via [`Record::copy_from_bytes_slice`],
+//! since a deserializer's `Vec<u8>` makes no alignment guarantee to cast in place.
+
+#![cfg(feature = "serde")]
+
+use serde::de::Error as DeError;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{QueryBuf, Record, SeqRecord, SharedRecords};
+
+/// On-the-wire shape shared by [`QueryBuf`] and [`SharedRecords`]: the record
+/// count alongside the raw transmuted bytes.
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Records")]
+struct Raw {
+    length: usize,
+    bytes: Vec<u8>,
+}
+
+impl Raw {
+    fn from_records<T: Record>(records: &[T]) -> Self {
+        Self {
+            length: records.len(),
+            bytes: Record::to_bytes_slice(records).to_vec(),
+        }
+    }
+
+    fn into_records<T: SeqRecord + Record + Copy, E: DeError>(self) -> Result<Vec<T>, E> {
+        let size = T::size();
+        if self.bytes.len() % size != 0 {
+            return Err(E::custom("byte length is not a multiple of the record size"));
+        }
+
+        // `self.bytes` came from a deserializer, with no alignment guarantee of its
+        // own, so cast via a fresh, properly aligned copy instead of in place --
+        // `T::from_bytes_slice` would otherwise panic on a bad alignment rather than
+        // letting us return a clean error.
+        let records = T::copy_from_bytes_slice(&self.bytes);
+        if records.len() != self.length {
+            return Err(E::custom("record count does not match the byte length"));
+        }
+
+        Ok(records)
+    }
+}
+
+impl<T: SeqRecord + Record> Serialize for QueryBuf<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Raw::from_records(self.records()).serialize(serializer)
+    }
+}
+
+impl<'de, T: SeqRecord + Record + Copy> Deserialize<'de> for QueryBuf<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let records = Raw::deserialize(deserializer)?.into_records::<T, D::Error>()?;
+
+        let mut buf = QueryBuf::new(records.len().max(1));
+        if !records.is_empty() {
+            buf.extend(&records);
+        }
+
+        Ok(buf)
+    }
+}
+
+impl<T: SeqRecord + Record + Copy> Serialize for SharedRecords<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Raw::from_records(self.records()).serialize(serializer)
+    }
+}
+
+impl<'de, T: SeqRecord + Record + Copy> Deserialize<'de> for SharedRecords<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let records = Raw::deserialize(deserializer)?.into_records::<T, D::Error>()?;
+        Ok(SharedRecords::from_records(records))
+    }
+}
+
+#[cfg(test)]
+#[cfg(any(feature = "zerocopy", feature = "bytemuck"))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "bytemuck")]
+    use bytemuck::{Pod, Zeroable};
+
+    #[cfg(feature = "zerocopy")]
+    use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+    const CAPACITY: usize = 1024;
+
+    #[cfg(feature = "zerocopy")]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, FromBytes, IntoBytes, Immutable, KnownLayout)]
+    struct Log(u64);
+
+    #[cfg(feature = "bytemuck")]
+    #[repr(transparent)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+    struct Log(u64);
+
+    impl SeqRecord for Log {
+        fn seq_no(&self) -> u64 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn query_buf_round_trips_through_json() {
+        let mut buf = QueryBuf::new(CAPACITY);
+        let records: Vec<_> = (1..=CAPACITY as u64).map(Log).collect();
+        buf.extend(&records);
+
+        let json = serde_json::to_string(&buf).unwrap();
+        let restored: QueryBuf<Log> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.records(), &records);
+    }
+
+    #[test]
+    fn shared_records_round_trips_through_json() {
+        let shared = SharedRecords::from_records((1..=CAPACITY as u64).map(Log).collect());
+
+        let json = serde_json::to_string(&shared).unwrap();
+        let restored: SharedRecords<Log> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.records(), shared.records());
+    }
+
+    #[test]
+    fn corrupted_byte_length_is_rejected() {
+        let raw = Raw {
+            length: 1,
+            bytes: vec![0u8; Log::size() + 1],
+        };
+
+        let json = serde_json::to_string(&raw).unwrap();
+        let restored: Result<QueryBuf<Log>, _> = serde_json::from_str(&json);
+        assert!(restored.is_err());
+    }
+}
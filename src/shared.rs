@@ -0,0 +1,207 @@
+//! Definition of a reference-counted, zero-copy view over queried records.
+
+use std::ops::{Bound, Deref, RangeBounds};
+use std::sync::Arc;
+
+use crate::SeqRecord;
+
+/// An immutable, reference-counted view over a contiguous run of records.
+///
+/// Modeled on [`bytes::Bytes`]: [`Clone`] and [`SharedRecords::slice`] are O(1)
+/// and never copy or reallocate the backing allocation, so query results can be
+/// fanned out to multiple readers (including across threads) without
+/// duplicating records.
+#[derive(Debug, Clone)]
+pub struct SharedRecords<T> {
+    records: Arc<[T]>,
+    start: usize,
+    len: usize,
+}
+
+impl<T: SeqRecord> SharedRecords<T> {
+    /// Wrap an owned run of records for sharing.
+    ///
+    /// # Arguments
+    ///
+    /// * `records` - Records this view should hold.
+    pub(crate) fn from_records(records: Vec<T>) -> Self {
+        let len = records.len();
+        Self {
+            records: Arc::from(records),
+            start: 0,
+            len,
+        }
+    }
+
+    /// Number of records held in this view.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether this view holds no records.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Slice of records this view covers.
+    pub fn records(&self) -> &[T] {
+        &self.records[self.start..self.start + self.len]
+    }
+
+    /// Create a new view over a sub-range of this one.
+    ///
+    /// Shares the same backing allocation as `self`; no records are copied.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if `range` falls outside `0..self.len()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - Logical range, relative to this view, to slice.
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> Self {
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n + 1,
+            Bound::Unbounded => 0,
+        };
+
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n + 1,
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => self.len,
+        };
+
+        assert!(start <= end && end <= self.len, "slice out of bounds");
+
+        Self {
+            records: Arc::clone(&self.records),
+            start: self.start + start,
+            len: end - start,
+        }
+    }
+
+    /// Create a new view covering exactly `records`, as long as it is really a
+    /// sub-slice of this view's backing allocation.
+    ///
+    /// The check is by pointer range, not by content, mirroring [`bytes::Bytes::slice_ref`].
+    /// An empty `records` always succeeds and returns an empty view, rather than
+    /// panicking because an empty slice's pointer isn't guaranteed to fall within
+    /// the backing allocation (the same empty-slice fix [`bytes` shipped in 0.5.4](https://github.com/tokio-rs/bytes/pull/380)).
+    ///
+    /// # Panics
+    ///
+    /// * Panics if `records` is non-empty and does not fall within this view.
+    ///
+    /// # Arguments
+    ///
+    /// * `records` - Sub-slice, borrowed from [`SharedRecords::records`], to convert into a view.
+    pub fn slice_ref(&self, records: &[T]) -> Self {
+        if records.is_empty() {
+            return Self {
+                records: Arc::clone(&self.records),
+                start: self.start,
+                len: 0,
+            };
+        }
+
+        let this = self.records();
+        let this_start = this.as_ptr() as usize;
+        let this_end = this_start + std::mem::size_of_val(this);
+
+        let that_start = records.as_ptr() as usize;
+        let that_end = that_start + std::mem::size_of_val(records);
+
+        assert!(
+            that_start >= this_start && that_end <= this_end,
+            "records is not a sub-slice of this SharedRecords"
+        );
+
+        let offset = (that_start - this_start) / size_of::<T>();
+        Self {
+            records: Arc::clone(&self.records),
+            start: self.start + offset,
+            len: records.len(),
+        }
+    }
+}
+
+impl<T: SeqRecord> Deref for SharedRecords<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.records()
+    }
+}
+
+#[cfg(test)]
+#[cfg(any(feature = "zerocopy", feature = "bytemuck"))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "bytemuck")]
+    use bytemuck::{Pod, Zeroable};
+
+    #[cfg(feature = "zerocopy")]
+    use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+    const CAPACITY: usize = 1024;
+
+    #[cfg(feature = "zerocopy")]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, FromBytes, IntoBytes, Immutable, KnownLayout)]
+    struct Log(u64);
+
+    #[cfg(feature = "bytemuck")]
+    #[repr(transparent)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+    struct Log(u64);
+
+    impl SeqRecord for Log {
+        fn seq_no(&self) -> u64 {
+            self.0
+        }
+    }
+
+    fn records() -> SharedRecords<Log> {
+        SharedRecords::from_records((1..=CAPACITY as u64).map(Log).collect())
+    }
+
+    #[test]
+    fn slice() {
+        let shared = records();
+        let middle = shared.slice(256..768);
+
+        assert_eq!(middle.len(), 512);
+        assert_eq!(middle.records(), &shared.records()[256..768]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn slice_out_of_bounds_panics() {
+        records().slice(0..CAPACITY + 1);
+    }
+
+    #[test]
+    fn slice_ref() {
+        let shared = records();
+        let sub = &shared.records()[256..768];
+
+        let view = shared.slice_ref(sub);
+        assert_eq!(view.records(), sub);
+    }
+
+    #[test]
+    fn slice_ref_empty_slice_does_not_panic() {
+        let shared = records();
+        let view = shared.slice_ref(&[]);
+        assert!(view.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn slice_ref_foreign_slice_panics() {
+        let shared = records();
+        let other: Vec<_> = (1..=CAPACITY as u64).map(Log).collect();
+        shared.slice_ref(&other);
+    }
+}
@@ -0,0 +1,95 @@
+//! Definition of a zero-copy view capped at a maximum number of records.
+
+use crate::SeqRecord;
+
+/// A zero-copy view exposing at most some number of records from a backing
+/// slice, inspired by [`bytes::buf::Take`].
+///
+/// Cheap to construct (just a capped sub-slice of the backing records, no copy) and
+/// composable with [`Chain`](crate::Chain) and [`SharedRecords`](crate::SharedRecords),
+/// so a caller can ask for "up to N records starting at seq_no X" and get a
+/// bounded, zero-copy view back for backpressure-aware paging.
+#[derive(Debug, Clone, Copy)]
+pub struct Take<'a, T> {
+    records: &'a [T],
+}
+
+impl<'a, T> Take<'a, T> {
+    /// Cap `records` at `n`, without copying.
+    ///
+    /// # Arguments
+    ///
+    /// * `records` - Backing records to cap.
+    /// * `n` - Maximum number of records this view exposes.
+    pub fn new(records: &'a [T], n: usize) -> Self {
+        Self {
+            records: &records[..n.min(records.len())],
+        }
+    }
+
+    /// Records exposed by this view.
+    pub fn records(&self) -> &'a [T] {
+        self.records
+    }
+
+    /// Number of records exposed by this view.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Whether this view exposes no records.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+}
+
+impl<'a, T: SeqRecord> From<Take<'a, T>> for &'a [T] {
+    fn from(take: Take<'a, T>) -> Self {
+        take.records
+    }
+}
+
+#[cfg(test)]
+#[cfg(any(feature = "zerocopy", feature = "bytemuck"))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "bytemuck")]
+    use bytemuck::{Pod, Zeroable};
+
+    #[cfg(feature = "zerocopy")]
+    use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+    #[cfg(feature = "zerocopy")]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, FromBytes, IntoBytes, Immutable, KnownLayout)]
+    struct Log(u64);
+
+    #[cfg(feature = "bytemuck")]
+    #[repr(transparent)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+    struct Log(u64);
+
+    impl SeqRecord for Log {
+        fn seq_no(&self) -> u64 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn caps_at_n_records() {
+        let records: Vec<_> = (1..=8u64).map(Log).collect();
+        let take = Take::new(&records, 3);
+
+        assert_eq!(take.len(), 3);
+        assert_eq!(take.records(), &records[..3]);
+    }
+
+    #[test]
+    fn n_larger_than_backing_slice_is_not_an_error() {
+        let records: Vec<_> = (1..=4u64).map(Log).collect();
+        let take = Take::new(&records, 100);
+
+        assert_eq!(take.len(), 4);
+        assert_eq!(take.records(), &records[..]);
+    }
+}